@@ -0,0 +1,138 @@
+use crate::paths;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Whether a file is a mood's report art or its pomodoro break art.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    Art,
+    Break,
+}
+
+/// One plain-text art file.
+#[derive(Debug)]
+pub struct ArtFile {
+    pub path: PathBuf,
+    /// Lowercased, as mood names are
+    pub mood: String,
+    pub kind: Kind,
+    /// `calm` in `zen.calm.txt` or `zen.break.calm.txt`
+    pub variant: Option<String>,
+    pub text: String,
+}
+
+/// Every art file in [`art_dir`]: each subdirectory is a pack, read in name
+/// order, then the files directly inside it.
+#[derive(Debug, Default)]
+pub struct ArtPack {
+    pub files: Vec<ArtFile>,
+    /// Why files that couldn't be used were left out
+    pub skipped: Vec<String>,
+}
+
+impl ArtPack {
+    /// An empty pack if there is no art directory.
+    pub fn load() -> Result<ArtPack, String> {
+        match art_dir().filter(|dir| dir.is_dir()) {
+            Some(dir) => ArtPack::load_dir(&dir),
+            None => Ok(ArtPack::default()),
+        }
+    }
+
+    fn load_dir(dir: &Path) -> Result<ArtPack, String> {
+        let (mut packs, mut loose) = (Vec::new(), Vec::new());
+        for entry in fs::read_dir(dir).map_err(|err| format!("could not read {}: {}", dir.display(), err))? {
+            let path = entry.map_err(|err| format!("could not read {}: {}", dir.display(), err))?.path();
+            if path.is_dir() {
+                packs.push(path);
+            } else {
+                loose.push(path);
+            }
+        }
+        packs.sort();
+
+        let mut art = ArtPack::default();
+        for pack in packs {
+            let entries = fs::read_dir(&pack).map_err(|err| format!("could not read {}: {}", pack.display(), err))?;
+            let paths = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_file());
+            art.read_files(paths);
+        }
+        art.read_files(loose.into_iter());
+        Ok(art)
+    }
+
+    /// A mood's files of one kind, in the order they become variants.
+    pub fn for_mood(&self, mood: &str, kind: Kind) -> impl Iterator<Item = &ArtFile> {
+        let mood = mood.to_string();
+        self.files.iter().filter(move |file| file.mood == mood && file.kind == kind)
+    }
+
+    /// Add the `.txt` files among `paths`, each mood's main file before its
+    /// variants. Empty or unreadable files are skipped, not the whole pack.
+    fn read_files(&mut self, paths: impl Iterator<Item = PathBuf>) {
+        let mut files = Vec::new();
+        for path in paths {
+            let Some((mood, kind, variant)) = parse_name(&path) else { continue };
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    self.skipped.push(format!("could not read {}: {}", path.display(), err));
+                    continue;
+                }
+            };
+            let text = trim_blank_lines(&String::from_utf8_lossy(&bytes));
+            if text.is_empty() {
+                self.skipped.push(format!("{} is empty", path.display()));
+                continue;
+            }
+            files.push(ArtFile { path, mood, kind, variant, text });
+        }
+        files.sort_by(|a, b| (&a.mood, a.kind, &a.variant).cmp(&(&b.mood, b.kind, &b.variant)));
+        self.files.append(&mut files);
+    }
+}
+
+/// `<mood>.txt`, `<mood>.<variant>.txt`, `<mood>.break.txt` or
+/// `<mood>.break.<variant>.txt`.
+fn parse_name(path: &Path) -> Option<(String, Kind, Option<String>)> {
+    if path.extension()? != "txt" {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let mut parts = stem.splitn(3, '.');
+    let mood = parts.next().filter(|mood| !mood.is_empty())?.to_lowercase();
+    match (parts.next(), parts.next()) {
+        (None, _) => Some((mood, Kind::Art, None)),
+        (Some("break"), variant) => Some((mood, Kind::Break, variant.map(str::to_string))),
+        (Some(first), rest) => {
+            let variant = rest.map_or(first.to_string(), |rest| format!("{}.{}", first, rest));
+            Some((mood, Kind::Art, Some(variant)))
+        }
+    }
+}
+
+/// `text` without the blank lines around it. Spaces that indent the first
+/// line are part of the art and stay.
+fn trim_blank_lines(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.iter().position(|line| !line.trim().is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(start, |last| last + 1);
+    lines[start..end].join("\n")
+}
+
+/// Art as the report prints it, with a blank line above and below.
+pub fn art_text(text: &str) -> String {
+    format!("\n{}\n", trim_blank_lines(text))
+}
+
+/// Break art as the timer prints it.
+pub fn break_text(text: &str) -> String {
+    trim_blank_lines(text)
+}
+
+/// `$XDG_CONFIG_HOME/vibe/art`, where art files and packs go.
+pub fn art_dir() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join("art"))
+}
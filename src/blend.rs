@@ -0,0 +1,259 @@
+use crate::{
+    moods::{Mood, Variants},
+    palette::Palette,
+};
+use rand::{distributions::WeightedIndex, prelude::*};
+
+/// Most items kept from the interleaved music and stack lists.
+const MAX_ITEMS: usize = 6;
+
+/// Whether `input` asks for a blend like "focus+chill" rather than one mood.
+pub fn is_blend(input: &str) -> bool {
+    input.contains('+')
+}
+
+/// Split "focus:2+zen" into mood names and weights. A part without a weight
+/// counts once.
+pub fn parse(input: &str) -> Result<Vec<(&str, f64)>, String> {
+    let parts = input
+        .split('+')
+        .map(|part| {
+            let part = part.trim();
+            let (name, weight) = match part.split_once(':') {
+                Some((name, weight)) => {
+                    let weight: f64 = weight
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("'{}' has a weight that isn't a number", part))?;
+                    (name.trim(), weight)
+                }
+                None => (part, 1.0),
+            };
+            if name.is_empty() {
+                return Err(format!("'{}' has an empty part", input));
+            }
+            if !weight.is_finite() || weight <= 0.0 {
+                return Err(format!("'{}' needs a weight above zero", part));
+            }
+            Ok((name, weight))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    // Shares and picks are worked out from the total, so it must be a number
+    if !parts.iter().map(|(_, weight)| weight).sum::<f64>().is_finite() {
+        return Err(format!("'{}' has weights too large to add up", input));
+    }
+    Ok(parts)
+}
+
+/// Canonical name of a blend, with weights only where they aren't 1:
+/// "focus+chill", "focus:2+zen".
+pub fn name(parts: &[(&Mood, f64)]) -> String {
+    parts
+        .iter()
+        .map(|(mood, weight)| {
+            if *weight == 1.0 {
+                mood.name.clone()
+            } else {
+                format!("{}:{}", mood.name, weight)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Merge several moods into one. Lists are interleaved by weight, numbers and
+/// palettes averaged, the gradient runs through each mood's primary color,
+/// and anything that can't be mixed (art, editor theme, effect, ...) comes
+/// from the heaviest mood.
+pub fn blend(parts: &[(&Mood, f64)], rng: &mut impl Rng) -> Mood {
+    let total: f64 = parts.iter().map(|(_, w)| w).sum();
+    let average = |value: fn(&Mood) -> f64| {
+        let mean = parts.iter().map(|(m, w)| value(m) * w).sum::<f64>() / total;
+        (mean * 100.0).round() / 100.0
+    };
+    // First listed wins a tie
+    let lead = parts
+        .iter()
+        .fold(parts[0], |best, part| if part.1 > best.1 { *part } else { best })
+        .0;
+
+    let shares: Vec<String> = parts
+        .iter()
+        .map(|(mood, weight)| format!("{:.0}% {}", weight / total * 100.0, mood.name))
+        .collect();
+
+    let palettes: Vec<(Palette, f64)> = parts.iter().map(|(m, w)| (m.palette, *w)).collect();
+
+    let weights: Vec<f64> = parts.iter().map(|(_, w)| *w).collect();
+    let motto_pick = WeightedIndex::new(&weights).expect("blend weights are positive");
+    let motto = parts[motto_pick.sample(rng)].0.motto.clone();
+
+    let mut timer_emoji = String::new();
+    let mut blocked_sites: Vec<String> = Vec::new();
+    for (mood, _) in parts {
+        if !timer_emoji.contains(&mood.timer_emoji) {
+            timer_emoji.push_str(&mood.timer_emoji);
+        }
+        for site in &mood.blocked_sites {
+            if !blocked_sites.contains(site) {
+                blocked_sites.push(site.clone());
+            }
+        }
+    }
+
+    let music = interleave(parts, |m| &m.music);
+    let stack = interleave(parts, |m| &m.stack);
+    let ascii_art = lead
+        .ascii_art
+        .clone()
+        .or_else(|| parts.iter().find_map(|(m, _)| m.ascii_art.clone()));
+
+    Mood {
+        name: name(parts),
+        description: format!("Blend of {}", shares.join(", ")),
+        theme: blend_theme(lead, parts),
+        palette: Palette::mix(&palettes),
+        effect: lead.effect,
+        gradient: parts.iter().map(|(m, _)| m.palette.primary).collect(),
+        energy: average(|m| m.energy),
+        positivity: average(|m| m.positivity),
+        variants: Variants {
+            music: vec![music.clone()],
+            stacks: vec![stack.clone()],
+            mottos: vec![motto.clone()],
+            art: ascii_art.iter().cloned().collect(),
+            breaks: vec![lead.break_art.clone()],
+        },
+        variant: None,
+        music,
+        stack,
+        motto,
+        ascii_art,
+        cow: lead.cow.clone(),
+        bubble: lead.bubble,
+        timer_emoji,
+        timer_message: lead.timer_message.clone(),
+        break_art: lead.break_art.clone(),
+        blocked_sites,
+        vscode_theme: lead.vscode_theme.clone(),
+        vscode_theme_extension: lead.vscode_theme_extension.clone(),
+        vscode_zen: lead.vscode_zen,
+    }
+}
+
+/// The heaviest theme's leading words with the other themes' last words:
+/// "Deep Ocean Blue" and "Forest Green" make "Deep Ocean Green".
+fn blend_theme(lead: &Mood, parts: &[(&Mood, f64)]) -> String {
+    let words: Vec<&str> = lead.theme.split_whitespace().collect();
+    let head = match words.split_last() {
+        Some((_, rest)) if !rest.is_empty() => rest.join(" "),
+        _ => lead.theme.clone(),
+    };
+    let tails: Vec<&str> = parts
+        .iter()
+        .filter(|(m, _)| m.name != lead.name)
+        .filter_map(|(m, _)| m.theme.split_whitespace().last())
+        .collect();
+    if tails.is_empty() {
+        head
+    } else {
+        format!("{} {}", head, tails.join("-"))
+    }
+}
+
+/// Take items from each comma-separated list in turn, heavier moods getting
+/// proportionally more turns, skipping repeats.
+fn interleave(parts: &[(&Mood, f64)], field: fn(&Mood) -> &String) -> String {
+    let mut lists: Vec<std::vec::IntoIter<&str>> = parts
+        .iter()
+        .map(|(m, _)| field(m).split(',').map(str::trim).collect::<Vec<_>>().into_iter())
+        .collect();
+    let lightest = parts.iter().map(|(_, w)| *w).fold(f64::INFINITY, f64::min);
+    let mut credit = vec![0.0; parts.len()];
+    let mut items: Vec<&str> = Vec::new();
+
+    while items.len() < MAX_ITEMS && lists.iter().any(|l| l.len() > 0) {
+        for (i, list) in lists.iter_mut().enumerate() {
+            credit[i] += parts[i].1 / lightest;
+            while credit[i] >= 1.0 && items.len() < MAX_ITEMS {
+                let Some(item) = list.next() else { break };
+                credit[i] -= 1.0;
+                if !items.iter().any(|seen| seen.eq_ignore_ascii_case(item)) {
+                    items.push(item);
+                }
+            }
+        }
+    }
+    items.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moods::MoodRegistry;
+    use rand::rngs::StdRng;
+
+    /// A built-in mood with its own name, theme and music.
+    fn mood(name: &str, theme: &str, music: &str) -> Mood {
+        let mut mood = MoodRegistry::builtin().get("focus").unwrap().clone();
+        mood.name = name.to_string();
+        mood.theme = theme.to_string();
+        mood.music = music.to_string();
+        mood
+    }
+
+    #[test]
+    fn parses_weights() {
+        assert_eq!(parse("focus:2+zen").unwrap(), [("focus", 2.0), ("zen", 1.0)]);
+        assert_eq!(parse(" focus : 0.5 + zen ").unwrap(), [("focus", 0.5), ("zen", 1.0)]);
+    }
+
+    #[test]
+    fn rejects_bad_weights() {
+        for (input, problem) in [
+            ("a+", "empty part"),
+            ("a:0", "above zero"),
+            ("a:-1", "above zero"),
+            ("a:NaN", "above zero"),
+            ("a:inf", "above zero"),
+            ("a:lots", "isn't a number"),
+            ("a:1e308+b:1e308", "too large"),
+        ] {
+            let err = parse(input).unwrap_err();
+            assert!(err.contains(problem), "{}: {}", input, err);
+        }
+    }
+
+    #[test]
+    fn interleaves_in_proportion_to_weight() {
+        let a = mood("a", "A", "a1, a2, a3, a4, a5");
+        let b = mood("b", "B", "b1, b2, b3, b4, b5");
+        assert_eq!(interleave(&[(&a, 2.0), (&b, 1.0)], |m| &m.music), "a1, a2, b1, a3, a4, b2");
+        assert_eq!(interleave(&[(&a, 1.0), (&b, 1.0)], |m| &m.music), "a1, b1, a2, b2, a3, b3");
+        // Once one list runs out the other fills the rest, without repeats
+        let short = mood("c", "C", "a1, c1");
+        assert_eq!(interleave(&[(&short, 3.0), (&b, 1.0)], |m| &m.music), "a1, c1, b1, b2, b3, b4");
+    }
+
+    #[test]
+    fn theme_takes_the_leads_head_and_the_others_tails() {
+        let focus = mood("focus", "Deep Ocean Blue", "");
+        let chill = mood("chill", "Forest Green", "");
+        let zen = mood("zen", "Calm", "");
+        assert_eq!(blend_theme(&focus, &[(&focus, 2.0), (&chill, 1.0)]), "Deep Ocean Green");
+        assert_eq!(blend_theme(&focus, &[(&focus, 1.0), (&chill, 1.0), (&zen, 1.0)]), "Deep Ocean Green-Calm");
+        assert_eq!(blend_theme(&zen, &[(&zen, 1.0), (&chill, 1.0)]), "Calm Green");
+    }
+
+    #[test]
+    fn blend_is_named_and_led_by_the_heaviest() {
+        let registry = MoodRegistry::builtin();
+        let (focus, zen) = (registry.get("focus").unwrap(), registry.get("zen").unwrap());
+        let mut rng = StdRng::seed_from_u64(1);
+        let blended = blend(&[(focus, 1.0), (zen, 3.0)], &mut rng);
+        assert_eq!(blended.name, "focus+zen:3");
+        assert_eq!(blended.description, "Blend of 25% focus, 75% zen");
+        assert_eq!(blended.vscode_theme, zen.vscode_theme);
+        assert_eq!(blended.energy, ((focus.energy + 3.0 * zen.energy) / 4.0 * 100.0).round() / 100.0);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{panel, paths};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{fs, path::PathBuf};
+
+/// Widest a bubble's text is wrapped to, as in cowsay.
+pub const BUBBLE_WIDTH: usize = 40;
+
+const DEFAULT_EYES: &str = "oo";
+const DEFAULT_TONGUE: &str = "  ";
+
+/// Characters compiled into the binary. Files in `$XDG_CONFIG_HOME/vibe/cows`
+/// with the same name take precedence.
+const BUILTIN_COWS: &[(&str, &str)] = &[
+    ("cat", include_str!("../cows/cat.cow")),
+    ("default", include_str!("../cows/default.cow")),
+    ("robot", include_str!("../cows/robot.cow")),
+    ("tux", include_str!("../cows/tux.cow")),
+];
+
+/// Whether the character says its words or thinks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Bubble {
+    #[default]
+    Say,
+    Think,
+}
+
+/// A character from a `.cow` file, drawn under a bubble.
+#[derive(Debug)]
+pub struct Cow {
+    template: String,
+    /// From a `<<'EOC'` heredoc, which Perl leaves as it is
+    literal: bool,
+}
+
+impl Cow {
+    /// Read a `.cow` file: Perl that sets `$the_cow` from a heredoc. Only the
+    /// heredoc is used; any other code in the file is ignored.
+    pub fn parse(source: &str) -> Result<Cow, String> {
+        let mut lines = source.lines();
+        let opener = lines
+            .find(|line| line.contains("$the_cow") && line.contains("<<"))
+            .ok_or("no `$the_cow = <<EOC;` heredoc")?;
+        let tag = opener[opener.find("<<").expect("checked above") + 2..].trim().trim_end_matches(';').trim();
+        // <<'EOC' is taken literally; <<"EOC" and <<EOC interpolate
+        let literal = tag.starts_with('\'');
+        let terminator = tag.trim_matches(['"', '\'']);
+        if terminator.is_empty() {
+            return Err("the heredoc has no terminator".to_string());
+        }
+
+        let mut body = Vec::new();
+        for line in lines.by_ref() {
+            if line.trim_end() == terminator {
+                return Ok(Cow {
+                    template: body.join("\n"),
+                    literal,
+                });
+            }
+            body.push(line);
+        }
+        Err(format!("the heredoc never ends (no '{}' line)", terminator))
+    }
+
+    /// `text` in a bubble wrapped to `width` columns, with the character
+    /// below it.
+    pub fn say(&self, text: &str, bubble: Bubble, width: usize) -> String {
+        let thoughts = match bubble {
+            Bubble::Say => "\\",
+            Bubble::Think => "o",
+        };
+        let mut lines = balloon(text, bubble, width);
+        lines.push(if self.literal { self.template.clone() } else { interpolate(&self.template, thoughts) });
+        lines.join("\n")
+    }
+}
+
+/// The bubble itself:
+///
+/// ```text
+///  _______________      _______________
+/// / Flow state is \    ( Flow state is )
+/// \ the goal      /    ( the goal      )
+///  ---------------      ---------------
+/// ```
+fn balloon(text: &str, bubble: Bubble, width: usize) -> Vec<String> {
+    let lines = panel::wrap(text, width);
+    let widest = lines.iter().map(|line| panel::width(line)).max().unwrap_or(0);
+    let last = lines.len() - 1;
+    let mut balloon = vec![format!(" {}", "_".repeat(widest + 2))];
+    for (n, line) in lines.iter().enumerate() {
+        let (left, right) = match bubble {
+            Bubble::Think => ('(', ')'),
+            Bubble::Say if last == 0 => ('<', '>'),
+            Bubble::Say if n == 0 => ('/', '\\'),
+            Bubble::Say if n == last => ('\\', '/'),
+            Bubble::Say => ('|', '|'),
+        };
+        let padding = " ".repeat(widest - panel::width(line));
+        balloon.push(format!("{} {}{} {}", left, line, padding, right));
+    }
+    balloon.push(format!(" {}", "-".repeat(widest + 2)));
+    balloon
+}
+
+/// What Perl makes of a double-quoted heredoc: `$thoughts`, `$eyes` and
+/// `$tongue` (or `${thoughts}`, ...) filled in, other variables empty, and
+/// the escapes cows use (`\\`, `\@`, `\$`, and `\e` for color) resolved.
+fn interpolate(template: &str, thoughts: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('e') => out.push('\x1b'),
+                Some('n') => out.push('\n'),
+                Some(escaped) => out.push(escaped),
+                None => out.push('\\'),
+            },
+            '$' => {
+                let braced = chars.next_if_eq(&'{').is_some();
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                if braced {
+                    chars.next_if_eq(&'}');
+                }
+                out.push_str(match name.as_str() {
+                    // A lone "$" is just a dollar sign
+                    "" if !braced => "$",
+                    "thoughts" => thoughts,
+                    "eyes" => DEFAULT_EYES,
+                    "tongue" => DEFAULT_TONGUE,
+                    _ => "",
+                });
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// `$XDG_CONFIG_HOME/vibe/cows`, where extra `.cow` files go.
+pub fn cows_dir() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join("cows"))
+}
+
+/// A character by name: `<name>.cow` in [`cows_dir`], else a built-in one.
+pub fn load(name: &str) -> Result<Cow, String> {
+    if let Some(path) = cows_dir().map(|dir| dir.join(format!("{}.cow", name))).filter(|path| path.exists()) {
+        let bytes = fs::read(&path).map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        return Cow::parse(&String::from_utf8_lossy(&bytes)).map_err(|err| format!("{}: {}", path.display(), err));
+    }
+    match BUILTIN_COWS.iter().find(|(builtin, _)| *builtin == name) {
+        Some((_, source)) => Ok(Cow::parse(source).expect("built-in cows are valid")),
+        None => Err(format!("unknown cow '{}'. Available: {}", name, names().join(", "))),
+    }
+}
+
+/// Built-in characters, then any others in [`cows_dir`], sorted.
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_COWS.iter().map(|(name, _)| name.to_string()).collect();
+    let mut custom: Vec<String> = cows_dir()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            (path.extension()? == "cow" && !names.contains(&name)).then_some(name)
+        })
+        .collect();
+    custom.sort();
+    names.append(&mut custom);
+    names
+}
@@ -0,0 +1,265 @@
+use crate::moods::MoodRegistry;
+use colored::*;
+use serde::Serialize;
+
+/// Words that hint at each built-in mood. Matched after a light stemming
+/// pass, so "shipping" finds "ship".
+const LEXICON: &[(&str, &[&str])] = &[
+    ("focus", &[
+        "deadline", "ship", "concentrate", "deep", "serious", "exam", "study", "important", "debug",
+        "bug", "quiet", "zone", "locked",
+    ]),
+    ("chaotic", &[
+        "chaos", "messy", "random", "hackathon", "broken", "fire", "yolo", "weird", "experiment",
+        "panic", "caffeine", "everything",
+    ]),
+    ("sadboi", &[
+        "sad", "tired", "exhausted", "sleepy", "lonely", "down", "gloomy", "rain", "rainy",
+        "miserable", "burnt", "burnout", "drained", "heartbroken", "cry", "meh", "sigh", "monday",
+    ]),
+    ("energetic", &[
+        "excited", "pumped", "energy", "hyped", "fast", "run", "coffee", "awake", "motivated",
+        "ready", "sprint", "stoked",
+    ]),
+    ("chill", &[
+        "relax", "lazy", "sunday", "weekend", "slow", "easy", "cozy", "tea", "calm", "mellow",
+        "casual", "whatever",
+    ]),
+    ("creative", &[
+        "idea", "design", "draw", "paint", "art", "inspired", "invent", "prototype", "imagine",
+        "dream", "colorful", "sketch",
+    ]),
+    ("productive", &[
+        "ship", "need", "must", "todo", "task", "meeting", "deadline", "work", "office", "email",
+        "ticket", "efficient", "busy", "done", "finish",
+    ]),
+    ("nostalgic", &[
+        "old", "retro", "remember", "memory", "memories", "childhood", "classic", "legacy", "cobol",
+        "vintage", "arcade", "miss",
+    ]),
+    ("adventurous", &[
+        "new", "explore", "learn", "curious", "try", "unknown", "experimental", "travel", "bold",
+        "discover", "brave",
+    ]),
+    ("zen", &[
+        "peace", "peaceful", "breathe", "mindful", "meditate", "simple", "minimal", "quiet",
+        "balance", "clean", "calm", "still",
+    ]),
+];
+
+const NAME_WEIGHT: f64 = 3.0;
+const LEXICON_WEIGHT: f64 = 2.0;
+const DESCRIPTION_WEIGHT: f64 = 1.0;
+/// "tired but need to ship" is about shipping: words after a contrast count more
+const CONTRAST_BOOST: f64 = 1.5;
+
+const CONTRASTS: &[&str] = &["but", "though", "however", "yet", "although"];
+/// A word right after one of these ("not tired") says nothing about the mood
+const NEGATIONS: &[&str] = &["not", "no", "never", "don't", "dont", "isn't", "without"];
+const STOPWORDS: &[&str] = &["for", "and", "with", "the", "that", "this", "from", "into", "your"];
+
+/// One word of the input and the mood it pointed at.
+#[derive(Debug, Clone, Serialize)]
+pub struct WordMatch {
+    pub word: String,
+    pub mood: String,
+    pub weight: f64,
+    /// "name", "lexicon" or "description"
+    pub source: &'static str,
+    /// Came after "but" or similar, so counted extra
+    pub emphasized: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MoodScore {
+    pub mood: String,
+    pub score: f64,
+}
+
+/// The mood read from free text, and how it got there.
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection {
+    pub mood: String,
+    pub matches: Vec<WordMatch>,
+    /// Every mood that scored, best first
+    pub scores: Vec<MoodScore>,
+}
+
+/// Score `text` against the lexicon, mood names, aliases and descriptions.
+/// `None` if no word said anything about a mood.
+pub fn detect(registry: &MoodRegistry, text: &str) -> Option<Detection> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| c.is_ascii_punctuation() && c != '\'').to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut matches = Vec::new();
+    let mut boost = 1.0;
+    for (i, word) in words.iter().enumerate() {
+        if CONTRASTS.contains(&word.as_str()) {
+            boost = CONTRAST_BOOST;
+            continue;
+        }
+        if i > 0 && NEGATIONS.contains(&words[i - 1].as_str()) {
+            continue;
+        }
+        for (mood, weight, source) in word_hits(registry, word) {
+            matches.push(WordMatch {
+                word: word.clone(),
+                mood,
+                weight: weight * boost,
+                source,
+                emphasized: boost > 1.0,
+            });
+        }
+    }
+
+    let mut scores: Vec<MoodScore> = Vec::new();
+    for m in &matches {
+        match scores.iter_mut().find(|s| s.mood == m.mood) {
+            Some(score) => score.score += m.weight,
+            None => scores.push(MoodScore {
+                mood: m.mood.clone(),
+                score: m.weight,
+            }),
+        }
+    }
+    // Stable sort keeps first-mentioned moods ahead on ties
+    scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    Some(Detection {
+        mood: scores.first()?.mood.clone(),
+        matches,
+        scores,
+    })
+}
+
+/// Moods a single word points at. A mood name or alias beats the lexicon,
+/// which beats words that merely appear in a description.
+fn word_hits(registry: &MoodRegistry, word: &str) -> Vec<(String, f64, &'static str)> {
+    if let Some(mood) = registry.get(word) {
+        return vec![(mood.name.clone(), NAME_WEIGHT, "name")];
+    }
+
+    let stems = stems(word);
+    let from_lexicon: Vec<_> = LEXICON
+        .iter()
+        .filter(|(mood, words)| registry.get(mood).is_some() && words.iter().any(|w| stems.contains(w)))
+        .map(|(mood, _)| (mood.to_string(), LEXICON_WEIGHT, "lexicon"))
+        .collect();
+    if !from_lexicon.is_empty() {
+        return from_lexicon;
+    }
+
+    if word.len() < 4 || STOPWORDS.contains(&word) {
+        return Vec::new();
+    }
+    registry
+        .all()
+        .iter()
+        .filter(|mood| {
+            mood.description
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|w| stems.contains(&w.to_lowercase().as_str()))
+        })
+        .map(|mood| (mood.name.clone(), DESCRIPTION_WEIGHT, "description"))
+        .collect()
+}
+
+/// The word itself plus what's left after dropping a common suffix.
+fn stems(word: &str) -> Vec<&str> {
+    let mut stems = vec![word];
+    for suffix in ["ing", "ed", "es", "s", "ly"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.chars().count() >= 3 {
+                stems.push(stem);
+            }
+        }
+    }
+    // "shipping" -> "shipp" -> "ship"
+    let doubled: Vec<&str> = stems
+        .iter()
+        .filter_map(|s| {
+            let mut chars = s.char_indices().rev();
+            let (last_at, last) = chars.next()?;
+            let (_, before) = chars.next()?;
+            (s.chars().count() >= 4 && last == before).then(|| &s[..last_at])
+        })
+        .collect();
+    stems.extend(doubled);
+    stems
+}
+
+pub fn print_explanation(detection: &Detection) {
+    println!("{}", format!("🔍 Why {}?", detection.mood).bold());
+    let width = detection.matches.iter().map(|m| m.word.chars().count()).max().unwrap_or(0);
+    for m in &detection.matches {
+        let why = if m.emphasized { format!("({}, after a contrast)", m.source) } else { format!("({})", m.source) };
+        println!(
+            "   {}  → {} {} {}",
+            format!("{:<width$}", m.word, width = width).cyan(),
+            m.mood,
+            format!("+{:.1}", m.weight).green(),
+            why.dimmed()
+        );
+    }
+    let scores: Vec<String> = detection
+        .scores
+        .iter()
+        .take(4)
+        .enumerate()
+        .map(|(i, s)| {
+            let entry = format!("{} {:.1}", s.mood, s.score);
+            if i == 0 { entry.green().bold().to_string() } else { entry }
+        })
+        .collect();
+    println!("   {} {}", "Scores:".yellow(), scores.join(" · "));
+    if detection.scores.len() == 1 {
+        println!("   {}", "No runner-up: only one mood matched".dimmed());
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(text: &str) -> Option<String> {
+        detect(&MoodRegistry::builtin(), text).map(|detection| detection.mood)
+    }
+
+    #[test]
+    fn a_negated_word_says_nothing() {
+        assert_eq!(detected("tired"), Some("sadboi".to_string()));
+        assert_eq!(detected("not tired"), None);
+        assert_eq!(detected("not tired, just calm"), Some("chill".to_string()));
+    }
+
+    #[test]
+    fn words_after_a_contrast_count_more() {
+        assert_eq!(detected("tired but excited"), Some("energetic".to_string()));
+        assert_eq!(detected("excited but tired"), Some("sadboi".to_string()));
+
+        let detection = detect(&MoodRegistry::builtin(), "tired but excited").unwrap();
+        let excited = detection.matches.iter().find(|m| m.word == "excited").unwrap();
+        assert!(excited.emphasized);
+        assert_eq!(excited.weight, LEXICON_WEIGHT * CONTRAST_BOOST);
+    }
+
+    #[test]
+    fn stems_drop_suffixes_and_doubled_letters() {
+        assert!(stems("shipping").contains(&"ship"));
+        assert!(stems("relaxed").contains(&"relax"));
+        // Doubled letters are found by character, not byte
+        assert!(stems("ぁぁぁぁ").contains(&"ぁぁぁ"));
+        assert_eq!(stems("xぁ"), ["xぁ"]);
+        assert!(stems("caféé").contains(&"café"));
+    }
+
+    #[test]
+    fn non_ascii_input_does_not_panic() {
+        assert_eq!(detected("ぁぁぁぁ ñññ 😭😭"), None);
+        assert_eq!(detected("😭"), Some("sadboi".to_string()));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::palette::{self, Rgb};
+use clap::ValueEnum;
+use crossterm::{cursor, execute, terminal};
+use serde::Deserialize;
+use std::{
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// How a block of text is colored across its width and height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Effect {
+    /// The first gradient stop throughout
+    #[default]
+    Flat,
+    /// The gradient stops from left to right
+    Horizontal,
+    /// The gradient stops from top to bottom
+    Vertical,
+    /// Every hue, cycling diagonally
+    Rainbow,
+}
+
+/// Columns per trip round the color wheel in [`Effect::Rainbow`].
+const RAINBOW_PERIOD: f64 = 24.0;
+const RAINBOW_SATURATION: f64 = 0.7;
+
+/// An animation is this many frames, ending where it started.
+const FRAMES: u32 = 30;
+const FRAME_DELAY: Duration = Duration::from_millis(50);
+
+static ANIMATE: AtomicBool = AtomicBool::new(false);
+
+/// Let [`show`] animate for the rest of the run, for `--animate`.
+pub fn set_animation(on: bool) {
+    ANIMATE.store(on, Ordering::Relaxed);
+}
+
+/// `text` colored by `effect` across `stops`. `phase`, from 0 to 1, moves
+/// the colors along for animation; 0 and 1 look the same.
+pub fn render(text: &str, effect: Effect, stops: &[Rgb], phase: f64) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    render_lines(&lines, effect, stops, phase).join("\n")
+}
+
+/// [`render`] for text that is already split into lines.
+pub fn render_lines<S: AsRef<str>>(lines: &[S], effect: Effect, stops: &[Rgb], phase: f64) -> Vec<String> {
+    let columns = lines.iter().map(|line| line.as_ref().width()).max().unwrap_or(0);
+    let rows = lines.len();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(y, line)| {
+            let mut x = 0;
+            palette::fg_runs(line.as_ref().chars().map(|c| {
+                let color = color_at(effect, stops, (x, y), (columns, rows), phase);
+                x += c.width().unwrap_or(0);
+                (c, color)
+            }))
+        })
+        .collect()
+}
+
+/// Print `text` with `effect`. On a terminal, with animation turned on and
+/// room to redraw, the colors sweep through it for a moment first.
+pub fn show(text: &str, effect: Effect, stops: &[Rgb]) {
+    println!("{}", render(text, effect, stops, 0.0));
+    let rows = text.split('\n').count();
+    let fits = terminal::size().is_ok_and(|(_, height)| rows < usize::from(height));
+    if effect == Effect::Flat || !ANIMATE.load(Ordering::Relaxed) || !io::stdout().is_terminal() || !fits {
+        return;
+    }
+    for frame in 1..=FRAMES {
+        thread::sleep(FRAME_DELAY);
+        let _ = execute!(io::stdout(), cursor::MoveToPreviousLine(rows as u16));
+        println!("{}", render(text, effect, stops, f64::from(frame) / f64::from(FRAMES)));
+    }
+}
+
+fn color_at(effect: Effect, stops: &[Rgb], (x, y): (usize, usize), (columns, rows): (usize, usize), phase: f64) -> Rgb {
+    let along = |i: usize, n: usize| if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+    match effect {
+        Effect::Flat => stops[0],
+        Effect::Horizontal => gradient(stops, bounce(along(x, columns) + 2.0 * phase)),
+        Effect::Vertical => gradient(stops, bounce(along(y, rows) + 2.0 * phase)),
+        Effect::Rainbow => hue((x + 2 * y) as f64 / RAINBOW_PERIOD + phase),
+    }
+}
+
+/// Fold any position onto 0..1 and back, so a gradient shifted along by an
+/// animation sweeps back and forth instead of jumping.
+fn bounce(t: f64) -> f64 {
+    let t = t.rem_euclid(2.0);
+    if t > 1.0 {
+        2.0 - t
+    } else {
+        t
+    }
+}
+
+/// The color `t` (0 to 1) of the way along evenly spaced `stops`.
+fn gradient(stops: &[Rgb], t: f64) -> Rgb {
+    if stops.len() == 1 {
+        return stops[0];
+    }
+    let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let index = (position.floor() as usize).min(stops.len() - 2);
+    let fraction = position - index as f64;
+    Rgb::mix(&[(stops[index], 1.0 - fraction), (stops[index + 1], fraction)])
+}
+
+/// A bright color `turns` of the way round the color wheel.
+fn hue(turns: f64) -> Rgb {
+    let h = turns.rem_euclid(1.0) * 6.0;
+    let (v, s) = (1.0, RAINBOW_SATURATION);
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let channel = |value: f64| ((value + v - c) * 255.0).round() as u8;
+    Rgb(channel(r), channel(g), channel(b))
+}
@@ -0,0 +1,408 @@
+use crate::{panel, paths};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+/// Font used by `--banner` without a name, and for the timer's digits.
+pub const DEFAULT_FONT: &str = "block";
+
+/// Fonts compiled into the binary. Files in `$XDG_CONFIG_HOME/vibe/fonts`
+/// with the same name take precedence.
+const BUILTIN_FONTS: &[(&str, &str)] = &[
+    ("block", include_str!("../fonts/block.flf")),
+    ("mini", include_str!("../fonts/mini.flf")),
+];
+
+/// Characters every font defines, in file order: printable ASCII...
+const REQUIRED: std::ops::RangeInclusive<u32> = 32..=126;
+/// ...then these, which many older fonts leave out.
+const DEUTSCH: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+/// How neighbouring letters are joined, from the header's layout fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// Each letter at its full width
+    FullWidth,
+    /// Letters moved together until they touch
+    Fitting,
+    /// Letters overlapped by one more column where `rules` (the bits of the
+    /// FIGlet spec's horizontal smushing rules; none means any overlap)
+    /// allow it
+    Smushing { rules: u32 },
+}
+
+const RULE_EQUAL: u32 = 1;
+const RULE_UNDERSCORE: u32 = 2;
+const RULE_HIERARCHY: u32 = 4;
+const RULE_OPPOSITE_PAIR: u32 = 8;
+const RULE_BIG_X: u32 = 16;
+const RULE_HARDBLANK: u32 = 32;
+
+/// A FIGlet font: big letters made of ordinary characters.
+#[derive(Debug)]
+pub struct Font {
+    height: usize,
+    /// Stands for a space that letters can't be moved into
+    hardblank: char,
+    layout: Layout,
+    glyphs: HashMap<char, Vec<Vec<char>>>,
+}
+
+impl Font {
+    /// Read a `.flf` file's contents.
+    pub fn parse(source: &str) -> Result<Font, String> {
+        let mut lines = source.lines();
+        let header = lines.next().ok_or("the font file is empty")?;
+        let Some(rest) = header.strip_prefix("flf2a") else {
+            return Err("not a FIGlet font (the first line should start with 'flf2a')".to_string());
+        };
+        let mut rest = rest.chars();
+        let hardblank = rest.next().ok_or("the header has no hardblank character")?;
+        let fields: Vec<i64> = rest
+            .as_str()
+            .split_whitespace()
+            .map(|field| field.parse().map_err(|_| format!("the header has a field that isn't a number: '{}'", field)))
+            .collect::<Result<_, _>>()?;
+        let field = |i: usize| fields.get(i).copied();
+        let (Some(height), Some(old_layout), Some(comments)) = (field(0), field(3), field(4)) else {
+            return Err("the header is missing fields (height, baseline, max length, layout, comment lines)".to_string());
+        };
+        let height = usize::try_from(height).ok().filter(|h| *h > 0).ok_or("the height must be at least 1")?;
+        let layout = layout(old_layout, field(6));
+
+        let mut lines = lines.skip(usize::try_from(comments).unwrap_or(0));
+        let mut glyphs = HashMap::new();
+        for code in REQUIRED {
+            let glyph = read_glyph(&mut lines, height)
+                .ok_or_else(|| format!("the file ends before '{}' is defined", char::from_u32(code).unwrap_or('?')))?;
+            glyphs.insert(char::from_u32(code).expect("ASCII is valid"), glyph);
+        }
+        for code in DEUTSCH {
+            let Some(glyph) = read_glyph(&mut lines, height) else { break };
+            glyphs.insert(char::from_u32(code).expect("Latin-1 is valid"), glyph);
+        }
+        // Anything else is tagged with its code: "0x263A  WHITE SMILING FACE"
+        while let Some(tag) = lines.next() {
+            let code = tag.split_whitespace().next().and_then(parse_code);
+            let Some(glyph) = read_glyph(&mut lines, height) else { break };
+            if let Some(c) = code.and_then(char::from_u32) {
+                glyphs.insert(c, glyph);
+            }
+        }
+
+        Ok(Font {
+            height,
+            hardblank,
+            layout,
+            glyphs,
+        })
+    }
+
+    /// `text` in big letters, one string per row. Characters the font
+    /// doesn't have are left out.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        let mut rows: Vec<Vec<char>> = vec![Vec::new(); self.height];
+        let mut previous_width = 0;
+        for glyph in text.chars().filter_map(|c| self.glyphs.get(&c)) {
+            let width = glyph[0].len();
+            let overlap = self.overlap(&rows, glyph, previous_width);
+            for (row, glyph_row) in rows.iter_mut().zip(glyph) {
+                let start = row.len() - overlap;
+                for (k, &c) in glyph_row.iter().enumerate() {
+                    match row.get_mut(start + k) {
+                        Some(existing) => *existing = self.smush(*existing, c, previous_width, width).unwrap_or(c),
+                        None => row.push(c),
+                    }
+                }
+            }
+            previous_width = width;
+        }
+        rows.into_iter()
+            .map(|row| row.into_iter().map(|c| if c == self.hardblank { ' ' } else { c }).collect())
+            .collect()
+    }
+
+    /// [`render`](Font::render), breaking at spaces and `+` when the whole
+    /// of `text` is wider than `max` columns. `None` if a single word is
+    /// still too wide.
+    pub fn render_wrapped(&self, text: &str, max: usize) -> Option<Vec<String>> {
+        let fits = |lines: &[String]| lines.iter().all(|line| panel::width(line) <= max);
+        let whole = self.render(text);
+        if fits(&whole) {
+            return Some(whole);
+        }
+        let mut lines = Vec::new();
+        for word in text.split([' ', '+']).filter(|word| !word.is_empty()) {
+            let big = self.render(word);
+            if !fits(&big) {
+                return None;
+            }
+            lines.extend(big);
+        }
+        Some(lines)
+    }
+
+    /// How many columns `glyph` can slide back over what's been drawn.
+    fn overlap(&self, rows: &[Vec<char>], glyph: &[Vec<char>], previous_width: usize) -> usize {
+        if self.layout == Layout::FullWidth || rows[0].is_empty() {
+            return 0;
+        }
+        let width = glyph[0].len();
+        rows.iter()
+            .zip(glyph)
+            .map(|(row, glyph_row)| {
+                // Blank columns at the end of the line and the start of the
+                // letter can always be overlapped
+                let trailing = row.iter().rev().take_while(|c| **c == ' ').count();
+                let leading = glyph_row.iter().take_while(|c| **c == ' ').count();
+                let mut amount = trailing + leading;
+                let (Some(&left), Some(&right)) = (row.iter().rev().nth(trailing), glyph_row.get(leading)) else {
+                    return amount;
+                };
+                if self.smush(left, right, previous_width, width).is_some() {
+                    amount += 1;
+                }
+                amount
+            })
+            .min()
+            .unwrap_or(0)
+            .min(width)
+            .min(rows[0].len())
+    }
+
+    /// The one character that `left` and `right` become when overlapped,
+    /// if the layout allows it.
+    fn smush(&self, left: char, right: char, left_width: usize, right_width: usize) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+        let Layout::Smushing { rules } = self.layout else { return None };
+        // Overlapping one-column letters would make them vanish
+        if left_width < 2 || right_width < 2 {
+            return None;
+        }
+        if rules == 0 {
+            return Some(if right == self.hardblank { left } else { right });
+        }
+        if left == self.hardblank || right == self.hardblank {
+            let both = left == self.hardblank && right == self.hardblank;
+            return (both && rules & RULE_HARDBLANK != 0).then_some(left);
+        }
+        if rules & RULE_EQUAL != 0 && left == right {
+            return Some(left);
+        }
+        if rules & RULE_UNDERSCORE != 0 {
+            const BORDERS: &str = "|/\\[]{}()<>";
+            if left == '_' && BORDERS.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && BORDERS.contains(left) {
+                return Some(left);
+            }
+        }
+        if rules & RULE_HIERARCHY != 0 {
+            const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |c: char| CLASSES.iter().position(|class| class.contains(c));
+            match (class(left), class(right)) {
+                (Some(l), Some(r)) if l < r => return Some(right),
+                (Some(l), Some(r)) if l > r => return Some(left),
+                _ => {}
+            }
+        }
+        if rules & RULE_OPPOSITE_PAIR != 0 && ["[]", "][", "{}", "}{", "()", ")("].contains(&format!("{}{}", left, right).as_str()) {
+            return Some('|');
+        }
+        if rules & RULE_BIG_X != 0 {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// The horizontal layout from the header: the newer full layout field when
+/// there is one, else the old one (-1 full width, 0 fitting, above 0 the
+/// smushing rules).
+fn layout(old_layout: i64, full_layout: Option<i64>) -> Layout {
+    const FITTING: i64 = 64;
+    const SMUSHING: i64 = 128;
+    match full_layout {
+        Some(full) if full & SMUSHING != 0 => Layout::Smushing { rules: (full & 63) as u32 },
+        Some(full) if full & FITTING != 0 => Layout::Fitting,
+        Some(_) => Layout::FullWidth,
+        None if old_layout < 0 => Layout::FullWidth,
+        None if old_layout == 0 => Layout::Fitting,
+        None => Layout::Smushing { rules: (old_layout & 63) as u32 },
+    }
+}
+
+/// One letter: `height` lines, each ending in an end mark (usually "@")
+/// that's dropped along with any trailing whitespace.
+fn read_glyph<'a>(lines: &mut impl Iterator<Item = &'a str>, height: usize) -> Option<Vec<Vec<char>>> {
+    let mut rows: Vec<Vec<char>> = Vec::with_capacity(height);
+    for _ in 0..height {
+        let line = lines.next()?.trim_end();
+        let row = match line.chars().last() {
+            Some(mark) => line.trim_end_matches(mark),
+            None => line,
+        };
+        rows.push(row.chars().collect());
+    }
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, ' ');
+    }
+    Some(rows)
+}
+
+/// A code tag's number, written in decimal, hex ("0x263A") or octal ("0177").
+/// Negative codes are for FIGlet's own use and are skipped.
+fn parse_code(tag: &str) -> Option<u32> {
+    if let Some(hex) = tag.strip_prefix("0x").or_else(|| tag.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else if tag.len() > 1 && tag.starts_with('0') {
+        u32::from_str_radix(&tag[1..], 8).ok()
+    } else {
+        tag.parse().ok()
+    }
+}
+
+/// `$XDG_CONFIG_HOME/vibe/fonts`, where extra `.flf` files go.
+pub fn fonts_dir() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join("fonts"))
+}
+
+/// A font by name: `<name>.flf` in [`fonts_dir`], else a built-in one.
+pub fn load(name: &str) -> Result<Font, String> {
+    if let Some(path) = fonts_dir().map(|dir| dir.join(format!("{}.flf", name))).filter(|path| path.exists()) {
+        let bytes = fs::read(&path).map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        // Old fonts are often Latin-1; only the letter shapes matter
+        return Font::parse(&String::from_utf8_lossy(&bytes)).map_err(|err| format!("{}: {}", path.display(), err));
+    }
+    match BUILTIN_FONTS.iter().find(|(builtin, _)| *builtin == name) {
+        Some((_, source)) => Ok(Font::parse(source).expect("built-in fonts are valid")),
+        None => Err(format!("unknown font '{}'. Available: {}", name, names().join(", "))),
+    }
+}
+
+/// Built-in fonts, then any others in [`fonts_dir`], sorted.
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_FONTS.iter().map(|(name, _)| name.to_string()).collect();
+    let mut custom: Vec<String> = fonts_dir()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            (path.extension()? == "flf" && !names.contains(&name)).then_some(name)
+        })
+        .collect();
+    custom.sort();
+    names.append(&mut custom);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A font with `header`'s layout fields, `glyphs` for a few characters
+    /// and blanks for the rest of printable ASCII.
+    fn font(header: &str, height: usize, glyphs: &[(char, &[&str])]) -> Font {
+        let mut source = format!("flf2a$ {}\n", header);
+        for c in REQUIRED.filter_map(char::from_u32) {
+            let rows: Vec<&str> = match glyphs.iter().find(|(glyph, _)| *glyph == c) {
+                Some((_, rows)) => rows.to_vec(),
+                None => vec![""; height],
+            };
+            for (i, row) in rows.iter().enumerate() {
+                let mark = if i + 1 == height { "@@" } else { "@" };
+                source.push_str(&format!("{}{}\n", row, mark));
+            }
+        }
+        Font::parse(&source).unwrap()
+    }
+
+    /// "H" and "i" from FIGlet's standard font, with its header: rules 1-8
+    /// (equal, underscore, hierarchy, opposite pair) in both layout fields.
+    fn standard() -> Font {
+        font(
+            "6 5 16 15 0 0 24463",
+            6,
+            &[
+                ('H', &[" _   _ ", "| | | |", "| |_| |", "|  _  |", "|_| |_|", "       "]),
+                ('i', &[" _ ", "(_)", "| |", "| |", "|_|", "   "]),
+            ],
+        )
+    }
+
+    #[test]
+    fn parses_the_layout_fields() {
+        assert_eq!(standard().layout, Layout::Smushing { rules: 15 });
+        assert_eq!(layout(15, None), Layout::Smushing { rules: 15 });
+        assert_eq!(layout(0, None), Layout::Fitting);
+        assert_eq!(layout(-1, None), Layout::FullWidth);
+        assert_eq!(layout(15, Some(64)), Layout::Fitting);
+        assert_eq!(layout(15, Some(0)), Layout::FullWidth);
+    }
+
+    #[test]
+    fn smushes_like_figlet() {
+        // `figlet Hi`: the "i" overlaps the "H" by a column, "(" giving way
+        // to "|" by the hierarchy rule
+        assert_eq!(
+            standard().render("Hi"),
+            [" _   _ _ ", "| | | (_)", "| |_| | |", "|  _  | |", "|_| |_|_|", "         "]
+        );
+    }
+
+    #[test]
+    fn applies_each_smushing_rule() {
+        let glyphs: &[(char, &[&str])] = &[
+            ('a', &["x/"]),
+            ('b', &["\\y"]),
+            ('c', &["x]"]),
+            ('d', &["[y"]),
+            ('e', &["x_"]),
+            ('f', &["|y"]),
+            ('g', &["x$"]),
+            ('h', &["$y"]),
+            ('i', &["x>"]),
+            ('j', &["<y"]),
+            ('k', &["xx"]),
+        ];
+        let all = font("1 1 4 -1 0 0 191", 1, glyphs);
+        assert_eq!(all.render("ab"), ["x|y"], "big X");
+        assert_eq!(all.render("ij"), ["xXy"], "big X");
+        assert_eq!(all.render("cd"), ["x|y"], "opposite pair");
+        assert_eq!(all.render("ef"), ["x|y"], "underscore");
+        assert_eq!(all.render("gh"), ["x y"], "hardblank");
+        assert_eq!(all.render("kk"), ["xxx"], "equal");
+        assert_eq!(all.render("ak"), ["x/xx"], "no rule joins / and x");
+
+        // Only the equal rule: everything else just touches
+        let equal = font("1 1 4 -1 0 0 129", 1, glyphs);
+        assert_eq!(equal.render("ab"), ["x/\\y"]);
+        assert_eq!(equal.render("gh"), ["x  y"]);
+        assert_eq!(equal.render("kk"), ["xxx"]);
+
+        // No rules at all: the right letter wins
+        let universal = font("1 1 4 -1 0 0 128", 1, glyphs);
+        assert_eq!(universal.render("ab"), ["x\\y"]);
+        assert_eq!(universal.render("ga"), ["xx/"]);
+        assert_eq!(universal.render("ah"), ["x/y"], "but a hardblank never does");
+    }
+
+    #[test]
+    fn fitting_only_closes_the_gaps() {
+        let glyphs: &[(char, &[&str])] = &[('a', &["x "]), ('b', &[" y"])];
+        assert_eq!(font("1 1 4 0 0", 1, glyphs).render("ab"), ["xy"]);
+        assert_eq!(font("1 1 4 -1 0", 1, glyphs).render("ab"), ["x  y"]);
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Hosts file edited by `--focus-mode` unless `--hosts-file` says otherwise.
+pub const DEFAULT_HOSTS_FILE: &str = "/etc/hosts";
+
+const BLOCK_START: &str = "# >>> what-to-vibe focus mode >>>";
+const BLOCK_END: &str = "# <<< what-to-vibe focus mode <<<";
+
+/// Where the untouched hosts file is saved before the first block is added.
+pub fn backup_path(hosts: &Path) -> PathBuf {
+    let mut name = hosts.as_os_str().to_owned();
+    name.push(".vibe-backup");
+    PathBuf::from(name)
+}
+
+/// What [`block`] did.
+#[derive(Debug)]
+pub struct Blocked {
+    /// How many host names are now blocked
+    pub names: usize,
+    /// Where the untouched file was saved, if this run saved it
+    pub backup: Option<PathBuf>,
+}
+
+/// Add (or replace) our marked block so every site resolves to 0.0.0.0.
+pub fn block(hosts: &Path, sites: &[String]) -> Result<Blocked, String> {
+    let original = read_hosts(hosts)?;
+    let (mut updated, had_block) = strip_block(&original, hosts)?;
+
+    // Only back up a file we haven't touched yet, so the backup is always the
+    // pre-vibe state even if focus mode is switched on repeatedly.
+    let backup = if had_block {
+        None
+    } else {
+        let backup = backup_path(hosts);
+        fs::write(&backup, &original).map_err(|e| io_error("back up", &backup, e))?;
+        Some(backup)
+    };
+
+    let mut names: Vec<String> = Vec::new();
+    for name in sites.iter().flat_map(|site| host_names(site)) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(BLOCK_START);
+    updated.push('\n');
+    for name in &names {
+        updated.push_str(&format!("0.0.0.0 {}\n", name));
+    }
+    updated.push_str(BLOCK_END);
+    updated.push('\n');
+
+    fs::write(hosts, updated).map_err(|e| io_error("write", hosts, e))?;
+    Ok(Blocked {
+        names: names.len(),
+        backup,
+    })
+}
+
+/// Remove our block if present. Safe to call any number of times; returns
+/// whether anything was removed.
+pub fn unblock(hosts: &Path) -> Result<bool, String> {
+    let original = read_hosts(hosts)?;
+    let (updated, had_block) = strip_block(&original, hosts)?;
+    if had_block {
+        fs::write(hosts, updated).map_err(|e| io_error("write", hosts, e))?;
+    }
+    Ok(had_block)
+}
+
+fn read_hosts(hosts: &Path) -> Result<String, String> {
+    match fs::read_to_string(hosts) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(io_error("read", hosts, e)),
+    }
+}
+
+/// Return the file without our block, and whether a block was found. A start
+/// marker without an end marker means someone edited the block by hand, so we
+/// refuse to guess rather than risk deleting their entries.
+fn strip_block(contents: &str, hosts: &Path) -> Result<(String, bool), String> {
+    let mut kept = String::with_capacity(contents.len());
+    let mut in_block = false;
+    let mut found = false;
+
+    for line in contents.split_inclusive('\n') {
+        match line.trim_end() {
+            BLOCK_START => {
+                in_block = true;
+                found = true;
+            }
+            BLOCK_END if in_block => in_block = false,
+            _ if in_block => {}
+            _ => kept.push_str(line),
+        }
+    }
+
+    if in_block {
+        return Err(format!(
+            "{} has an unterminated focus mode block; restore it from {}",
+            hosts.display(),
+            backup_path(hosts).display()
+        ));
+    }
+    Ok((kept, found))
+}
+
+/// Turn a user-supplied site ("https://www.reddit.com/r/rust") into the host
+/// names worth blocking ("reddit.com", "www.reddit.com"). Subdomains such as
+/// "news.ycombinator.com" are blocked as given.
+fn host_names(site: &str) -> Vec<String> {
+    let site = site.trim().to_lowercase();
+    let site = site.split("://").last().unwrap_or_default();
+    let host = site.split(['/', ':']).next().unwrap_or_default();
+    let bare = host.strip_prefix("www.").unwrap_or(host);
+    if bare.is_empty() {
+        return Vec::new();
+    }
+    if bare.matches('.').count() == 1 {
+        vec![bare.to_string(), format!("www.{}", bare)]
+    } else {
+        vec![bare.to_string()]
+    }
+}
+
+fn io_error(action: &str, path: &Path, err: io::Error) -> String {
+    if err.kind() == io::ErrorKind::PermissionDenied {
+        format!(
+            "permission denied trying to {} {} (try sudo, or point --hosts-file at a copy)",
+            action,
+            path.display()
+        )
+    } else {
+        format!("failed to {} {}: {}", action, path.display(), err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOSTS: &str = "127.0.0.1 localhost\n::1 localhost\n";
+
+    /// A hosts file of its own in the temp directory, removed with its backup
+    /// when the test ends.
+    struct TempHosts(PathBuf);
+
+    impl TempHosts {
+        fn new(name: &str, contents: &str) -> TempHosts {
+            let path = std::env::temp_dir().join(format!("vibe-hosts-{}-{}", name, std::process::id()));
+            fs::write(&path, contents).unwrap();
+            TempHosts(path)
+        }
+
+        fn read(&self) -> String {
+            fs::read_to_string(&self.0).unwrap()
+        }
+    }
+
+    impl Drop for TempHosts {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+            let _ = fs::remove_file(backup_path(&self.0));
+        }
+    }
+
+    #[test]
+    fn block_twice_then_unblock_restores_the_file() {
+        let hosts = TempHosts::new("round-trip", HOSTS);
+        let sites = vec!["https://www.reddit.com/r/rust".to_string(), "news.ycombinator.com".to_string()];
+
+        let first = block(&hosts.0, &sites).unwrap();
+        assert_eq!(first.names, 3);
+        assert_eq!(first.backup, Some(backup_path(&hosts.0)));
+        let blocked = hosts.read();
+        assert!(blocked.starts_with(HOSTS));
+        for name in ["reddit.com", "www.reddit.com", "news.ycombinator.com"] {
+            assert!(blocked.contains(&format!("0.0.0.0 {}\n", name)), "{} not blocked", name);
+        }
+
+        // A second block replaces the first and keeps the original backup
+        let second = block(&hosts.0, &["youtube.com".to_string()]).unwrap();
+        assert_eq!(second.names, 2);
+        assert_eq!(second.backup, None);
+        let reblocked = hosts.read();
+        assert_eq!(reblocked.matches(BLOCK_START).count(), 1);
+        assert!(reblocked.contains("0.0.0.0 youtube.com\n"));
+        assert!(!reblocked.contains("reddit.com"));
+        assert_eq!(fs::read_to_string(backup_path(&hosts.0)).unwrap(), HOSTS);
+
+        assert!(unblock(&hosts.0).unwrap());
+        assert_eq!(hosts.read(), HOSTS);
+        assert!(!unblock(&hosts.0).unwrap());
+        assert_eq!(hosts.read(), HOSTS);
+    }
+
+    #[test]
+    fn block_adds_a_newline_before_the_block() {
+        let hosts = TempHosts::new("no-newline", "127.0.0.1 localhost");
+        block(&hosts.0, &["x.com".to_string()]).unwrap();
+        assert!(hosts.read().starts_with(&format!("127.0.0.1 localhost\n{}\n", BLOCK_START)));
+        unblock(&hosts.0).unwrap();
+        assert_eq!(hosts.read(), "127.0.0.1 localhost\n");
+    }
+
+    #[test]
+    fn strip_block_refuses_an_unterminated_block() {
+        let contents = format!("{}{}\n0.0.0.0 reddit.com\n", HOSTS, BLOCK_START);
+        let err = strip_block(&contents, Path::new("/etc/hosts")).unwrap_err();
+        assert!(err.contains("unterminated"), "{}", err);
+        assert!(err.contains("/etc/hosts.vibe-backup"), "{}", err);
+    }
+
+    #[test]
+    fn unterminated_block_leaves_the_file_alone() {
+        let contents = format!("{}{}\n0.0.0.0 reddit.com\n", HOSTS, BLOCK_START);
+        let hosts = TempHosts::new("unterminated", &contents);
+        assert!(block(&hosts.0, &["x.com".to_string()]).is_err());
+        assert!(unblock(&hosts.0).is_err());
+        assert_eq!(hosts.read(), contents);
+    }
+}
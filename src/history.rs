@@ -0,0 +1,168 @@
+use crate::{moods::Variant, paths};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+/// One line of `history.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: u64,
+    pub mood: String,
+    /// Which music, stack, motto and art were shown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<Variant>,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Event {
+    /// A vibe report was shown for `mood`
+    Pick { at: DateTime<Local> },
+    /// A `--timer` session, from start to the last countdown
+    Pomodoro {
+        started_at: DateTime<Local>,
+        ended_at: DateTime<Local>,
+        planned_minutes: u32,
+        actual_minutes: f64,
+        planned_pomodoros: u32,
+        completed_pomodoros: u32,
+        /// Pomodoros moved past with `s` before they ended
+        #[serde(default)]
+        skipped_pomodoros: u32,
+        /// False if the session was quit before the last pomodoro ended, or
+        /// any pomodoro was skipped
+        completed: bool,
+    },
+    /// A 1-5 score for the pick or session with id `entry`, which shares its
+    /// mood and variant
+    Rating {
+        at: DateTime<Local>,
+        entry: u64,
+        score: u8,
+    },
+}
+
+impl Entry {
+    pub fn at(&self) -> DateTime<Local> {
+        match &self.event {
+            Event::Pick { at } => *at,
+            Event::Pomodoro { started_at, .. } => *started_at,
+            Event::Rating { at, .. } => *at,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.event {
+            Event::Pick { .. } => "pick",
+            Event::Pomodoro { .. } => "pomodoro",
+            Event::Rating { .. } => "rating",
+        }
+    }
+}
+
+/// Which entries `vibe history` should show or delete.
+#[derive(Debug, Default)]
+pub struct Filter {
+    pub mood: Option<String>,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+}
+
+impl Filter {
+    pub fn matches(&self, entry: &Entry) -> bool {
+        let date = entry.at().date_naive();
+        self.mood.as_ref().is_none_or(|m| entry.mood.eq_ignore_ascii_case(m))
+            && self.since.is_none_or(|since| date >= since)
+            && self.until.is_none_or(|until| date <= until)
+    }
+}
+
+pub fn history_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("history.jsonl"))
+}
+
+/// Every entry, oldest first. A missing file is an empty history.
+pub fn load() -> Result<Vec<Entry>, String> {
+    let Some(path) = history_path() else {
+        return Ok(Vec::new());
+    };
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {}: {}", path.display(), n + 1, e))
+        })
+        .collect()
+}
+
+/// Variants shown for each mood, newest first.
+pub fn recent_variants(entries: &[Entry]) -> HashMap<String, Vec<Variant>> {
+    let mut recent: HashMap<String, Vec<Variant>> = HashMap::new();
+    for entry in entries.iter().rev() {
+        if let Some(variant) = entry.variant {
+            recent.entry(entry.mood.clone()).or_default().push(variant);
+        }
+    }
+    recent
+}
+
+/// Append an event for `mood`, giving it the next free id, which is returned.
+pub fn record(mood: &str, variant: Option<Variant>, event: Event) -> Result<u64, String> {
+    let path = history_path().ok_or("cannot locate a data directory (is $HOME set?)")?;
+    let id = load()?.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+    let entry = Entry {
+        id,
+        mood: mood.to_string(),
+        variant,
+        event,
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+    let line = serde_json::to_string(&entry).expect("history entries always serialize");
+    writeln!(file, "{}", line).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    Ok(id)
+}
+
+/// Remove entries for which `remove` returns true, returning how many went.
+pub fn delete_where(remove: impl Fn(&Entry) -> bool) -> Result<usize, String> {
+    let entries = load()?;
+    let before = entries.len();
+    let kept: Vec<Entry> = entries.into_iter().filter(|e| !remove(e)).collect();
+    let removed = before - kept.len();
+    if removed == 0 {
+        return Ok(0);
+    }
+
+    let path = history_path().ok_or("cannot locate a data directory (is $HOME set?)")?;
+    let mut contents = String::new();
+    for entry in &kept {
+        contents.push_str(&serde_json::to_string(entry).expect("history entries always serialize"));
+        contents.push('\n');
+    }
+    // Write to a sibling file first so a crash can't leave half a history
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, contents).map_err(|e| format!("failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("failed to replace {}: {}", path.display(), e))?;
+    Ok(removed)
+}
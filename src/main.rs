@@ -1,22 +1,121 @@
-use clap::Parser;
+mod art;
+mod blend;
+mod cowsay;
+mod detect;
+mod effects;
+mod figlet;
+mod focus;
+mod history;
+mod moods;
+mod output;
+mod palette;
+mod panel;
+mod paths;
+mod pomodoro;
+mod prefs;
+mod schedule;
+mod stats;
+mod tui;
+mod workspace;
+
+use art::{ArtPack, Kind};
+use chrono::{Local, NaiveDate};
+use clap::{Args, Parser, Subcommand};
 use colored::*;
-use rand::seq::SliceRandom;
-use std::{thread, time::Duration, io::{self, Write}};
-// Crossterm is still needed for the dependency, but we're using simpler methods now
+use cowsay::{Bubble, Cow};
+use effects::Effect;
+use figlet::Font;
+use history::Event;
+use moods::{Lookup, MoodRegistry};
+use output::OutputFormat;
+use palette::{ColorChoice, Palette, Rgb};
+use panel::Colors;
+use pomodoro::{Outcome, Phase, Plan};
+use prefs::Prefs;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::Serialize;
+use std::{collections::HashMap, thread, time::Duration, io::{self, IsTerminal}, path::{Path, PathBuf}};
 
 #[derive(Parser)]
 #[command(name = "vibe")]
 #[command(about = "Get themed responses based on your current mood")]
 #[command(version)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
-    /// Your current mood (optional - will be randomly selected if not provided)
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Your current mood (optional - will be randomly selected if not provided).
+    /// Free text like "tired but need to ship" is matched to the closest mood,
+    /// and "focus+chill" or "focus:2+zen" blends several
     #[arg(value_name = "MOOD")]
     mood: Option<String>,
 
+    /// Same as MOOD, for use after an option that takes an optional value
+    /// (`--timer --mood focus+chill`)
+    #[arg(long = "mood", value_name = "MOOD", conflicts_with = "mood")]
+    mood_option: Option<String>,
+
+    /// Pick the mood nearest this energy level, from 0 (drowsy) to 1 (wired)
+    #[arg(long, value_name = "0-1", value_parser = parse_unit, requires = "positivity",
+          conflicts_with_all = ["mood", "mood_option"])]
+    energy: Option<f64>,
+
+    /// Pick the mood nearest this positivity, from 0 (miserable) to 1 (elated)
+    #[arg(long, value_name = "0-1", value_parser = parse_unit, requires = "energy")]
+    positivity: Option<f64>,
+
+    /// Show which words picked the mood when it was read from free text
+    #[arg(long)]
+    explain: bool,
+
     /// Show ASCII art for the selected mood
     #[arg(long)]
     ascii: bool,
 
+    /// Have this character (a built-in one, or a .cow file in the config
+    /// cows directory) say the motto in place of the art. Implies --ascii
+    #[arg(long, value_name = "NAME")]
+    cow: Option<String>,
+
+    /// Put the motto in a thought bubble instead of a speech bubble
+    #[arg(long)]
+    think: bool,
+
+    /// Color art, the motto and headers with this effect instead of each
+    /// mood's own
+    #[arg(long, value_enum, value_name = "EFFECT")]
+    effect: Option<Effect>,
+
+    /// Let gradient and rainbow art shimmer for a moment on a terminal
+    #[arg(long)]
+    animate: bool,
+
+    /// Show the mood's name as a big banner in a FIGlet font (built-in
+    /// "block" or "mini", or a .flf file in the config fonts directory); the
+    /// timer counts down in the same font
+    #[arg(long, value_name = "FONT", num_args = 0..=1, default_missing_value = figlet::DEFAULT_FONT)]
+    banner: Option<String>,
+
+    /// Print the vibe in a machine-readable format instead of the colored report
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    format: Option<OutputFormat>,
+
+    /// When to color output: on a terminal (auto, the default, which also
+    /// honors NO_COLOR and CLICOLOR_FORCE), always or never
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto, global = true)]
+    color: ColorChoice,
+
+    /// Seed the random choices (mood, variants, blend motto) so a run can be
+    /// repeated exactly. Recently shown variants are not skipped
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+
+    /// When the mood is picked at random, show the time-of-day and weekday
+    /// weights behind the pick
+    #[arg(long)]
+    why: bool,
+
     /// Enable GODMODE: override all moods with elite hacker drama
     #[arg(long)]
     godmode: bool,
@@ -25,18 +124,52 @@ struct Cli {
     #[arg(long, value_name = "MINUTES", num_args = 0..=1, default_missing_value = "25")]
     timer: Option<u32>,
 
+    /// Number of pomodoros to run back to back
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "timer",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    cycles: u32,
+
+    /// Length of the break between pomodoros, in minutes
+    #[arg(long, value_name = "MINUTES", default_value_t = 5, requires = "timer")]
+    short_break: u32,
+
+    /// Length of the long break, in minutes
+    #[arg(long, value_name = "MINUTES", default_value_t = 15, requires = "timer")]
+    long_break: u32,
+
+    /// Take a long break after every N pomodoros
+    #[arg(long, value_name = "N", default_value_t = 4, requires = "timer",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    long_break_every: u32,
+
     /// Block distracting websites (requires sudo)
     #[arg(long)]
     focus_mode: bool,
 
+    /// Remove the focus mode block from the hosts file
+    #[arg(long)]
+    unblock: bool,
+
+    /// Hosts file edited by --focus-mode and --unblock
+    #[arg(long, value_name = "PATH", default_value = focus::DEFAULT_HOSTS_FILE)]
+    hosts_file: PathBuf,
+
     /// Set up VS Code workspace for the vibe
     #[arg(long)]
     workspace: bool,
 
+    /// With --workspace, show the changes as a diff instead of writing them
+    #[arg(long)]
+    dry_run: bool,
+
     /// Launch interactive vibe selector
     #[arg(long)]
     interactive: bool,
 
+    /// Pick a mood by moving a cursor around an energy/positivity grid
+    #[arg(long, conflicts_with = "interactive")]
+    grid: bool,
+
     /// [HONEYPOT] Secret mode (shh...)
     #[arg(long, hide = true)]
     secret_mode: bool,
@@ -50,334 +183,503 @@ struct Cli {
     api_key: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Subcommand)]
+enum Command {
+    /// List or delete past mood picks and pomodoro sessions
+    History {
+        #[command(subcommand)]
+        action: Option<HistoryAction>,
+
+        #[command(flatten)]
+        filter: HistoryFilter,
+
+        /// Only show the N most recent matching entries
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+    },
+    /// Focus totals, streaks, favorite moods and an activity heatmap
+    Stats {
+        /// How many weeks the heatmap covers (up to ten years)
+        #[arg(long, value_name = "N", default_value_t = 26, value_parser = clap::value_parser!(u32).range(1..=520))]
+        weeks: u32,
+    },
+    /// The vibe of the day: the same mood and variants for everyone on the
+    /// same date (and team)
+    Today {
+        /// Share a daily vibe with everyone using the same team name
+        #[arg(long, value_name = "NAME")]
+        team: Option<String>,
+
+        /// Show the vibe of another day (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        date: Option<NaiveDate>,
+
+        /// Show ASCII art for the mood of the day
+        #[arg(long)]
+        ascii: bool,
+
+        /// Print the vibe in a machine-readable format instead of the colored report
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        format: Option<OutputFormat>,
+    },
+    /// Rate the last vibe or pomodoro, so random picks lean towards what you
+    /// like
+    Rate {
+        /// 1 to 5, or up/down (+/-) for a thumb
+        #[arg(value_name = "SCORE", value_parser = prefs::parse_score, allow_hyphen_values = true)]
+        score: u8,
+
+        /// Rate this history entry instead of the latest one
+        #[arg(long, value_name = "ID")]
+        id: Option<u64>,
+    },
+    /// Show the mood and variant weights learned from your ratings
+    Prefs {
+        /// Forget the ratings (only this mood's with --mood)
+        #[arg(long)]
+        reset: bool,
+
+        /// With --reset, only forget ratings for this mood
+        #[arg(long, requires = "reset")]
+        mood: Option<String>,
+    },
+    /// List the moods' art and break art, or preview a mood's
+    Art {
+        #[command(subcommand)]
+        action: ArtAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    /// Delete entries by id, or every entry matching the filters
+    Delete {
+        /// Ids as shown by `vibe history`
+        #[arg(value_name = "ID")]
+        ids: Vec<u64>,
+
+        #[command(flatten)]
+        filter: HistoryFilter,
+
+        /// Delete the whole history
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ArtAction {
+    /// How many art and break art variants each mood has, and their files
+    List,
+    /// Print every variant of a mood's art
+    Show {
+        /// Mood name, alias or unique prefix
+        mood: String,
+
+        /// Show the pomodoro break art instead
+        #[arg(long = "break")]
+        break_art: bool,
+    },
+}
+
+#[derive(Args)]
+struct HistoryFilter {
+    /// Only entries for this mood
+    #[arg(long)]
+    mood: Option<String>,
+
+    /// Only entries on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    since: Option<NaiveDate>,
+
+    /// Only entries on or before this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    until: Option<NaiveDate>,
+}
+
+impl HistoryFilter {
+    fn is_empty(&self) -> bool {
+        self.mood.is_none() && self.since.is_none() && self.until.is_none()
+    }
+
+    /// The filter, with `--mood` resolved like a mood on the command line.
+    fn into_filter(self, registry: &MoodRegistry, entries: &[history::Entry]) -> history::Filter {
+        history::Filter {
+            mood: self.mood.map(|mood| history_mood(registry, entries, &mood)),
+            since: self.since,
+            until: self.until,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
 struct VibeResponse {
-    theme: &'static str,
-    music: &'static str,
-    stack: &'static str,
-    motto: &'static str,
-}
-
-fn get_vibe_response(mood: &str) -> Option<VibeResponse> {
-    match mood.to_lowercase().as_str() {
-        "focus" => Some(VibeResponse {
-            theme: "Deep Ocean Blue",
-            music: "Lo-fi beats, classical piano, ambient nature sounds",
-            stack: "Rust, TypeScript, PostgreSQL, Docker",
-            motto: "Flow state is the goal state",
-        }),
-        "chaotic" => Some(VibeResponse {
-            theme: "Neon Cyberpunk",
-            music: "EDM, industrial rock, experimental electronic",
-            stack: "Python, JavaScript, MongoDB, Redis, WebSockets",
-            motto: "Embrace the chaos, create order",
-        }),
-        "sadboi" => Some(VibeResponse {
-            theme: "Melancholic Purple",
-            music: "Indie folk, post-rock, sad piano ballads",
-            stack: "Go, React, SQLite, simple APIs",
-            motto: "Code through the feels",
-        }),
-        "energetic" => Some(VibeResponse {
-            theme: "Sunset Orange",
-            music: "Rock, punk, high-energy electronic, workout beats",
-            stack: "Node.js, React, Firebase, WebRTC",
-            motto: "Code like you're running out of time",
-        }),
-        "chill" => Some(VibeResponse {
-            theme: "Forest Green",
-            music: "Jazz, acoustic, nature sounds, smooth R&B",
-            stack: "Python, Flask, SQLite, simple HTML/CSS",
-            motto: "Take it easy, build it right",
-        }),
-        "creative" => Some(VibeResponse {
-            theme: "Rainbow Spectrum",
-            music: "Alternative, indie, experimental, world music",
-            stack: "JavaScript, Three.js, WebGL, creative coding",
-            motto: "Art and code are one",
-        }),
-        "productive" => Some(VibeResponse {
-            theme: "Corporate Blue",
-            music: "Instrumental hip-hop, productivity playlists, white noise",
-            stack: "Java, Spring Boot, MySQL, Kubernetes",
-            motto: "Efficiency is the ultimate form of beauty",
-        }),
-        "nostalgic" => Some(VibeResponse {
-            theme: "Retro Sepia",
-            music: "80s synthwave, classic rock, vinyl crackle",
-            stack: "C++, OpenGL, legacy systems, retro computing",
-            motto: "The future is built on the past",
-        }),
-        "adventurous" => Some(VibeResponse {
-            theme: "Aurora Borealis",
-            music: "Epic orchestral, adventure soundtracks, tribal drums",
-            stack: "Rust, WebAssembly, blockchain, edge computing",
-            motto: "Explore the unknown, build the impossible",
-        }),
-        "zen" => Some(VibeResponse {
-            theme: "Minimalist White",
-            music: "Meditation, zen gardens, silence, minimal ambient",
-            stack: "Haskell, functional programming, pure functions",
-            motto: "Less is more, simplicity is complexity resolved",
-        }),
-        _ => None,
-    }
-}
-
-fn print_vibe_response(response: &VibeResponse) {
+    #[serde(skip)]
+    mood: String,
+    theme: String,
+    music: String,
+    stack: String,
+    motto: String,
+    energy: f64,
+    positivity: f64,
+    palette: Palette,
+    #[serde(skip)]
+    effect: Effect,
+    #[serde(skip)]
+    gradient: Vec<Rgb>,
+}
+
+fn get_vibe_response(registry: &MoodRegistry, mood: &str) -> Option<VibeResponse> {
+    registry.get(mood).map(|mood| VibeResponse {
+        mood: mood.name.clone(),
+        theme: mood.theme.clone(),
+        music: mood.music.clone(),
+        stack: mood.stack.clone(),
+        motto: mood.motto.clone(),
+        energy: mood.energy,
+        positivity: mood.positivity,
+        palette: mood.palette,
+        effect: mood.effect,
+        gradient: mood.gradient_stops(),
+    })
+}
+
+/// What `--format` serializes. Field names are part of the CLI's interface, so
+/// only ever add to them.
+#[derive(Serialize)]
+struct VibeReport<'a> {
+    mood: &'a str,
+    /// What the user typed, when it was an alias or prefix of `mood`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested: Option<&'a str>,
+    #[serde(flatten)]
+    response: &'a VibeResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ascii_art: Option<&'a str>,
+    /// With `--explain`, how free text was turned into `mood`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation: Option<detect::Detection>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    day: Option<Day<'a>>,
+}
+
+/// Which day `vibe today` showed, and for which team.
+#[derive(Serialize)]
+struct Day<'a> {
+    date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    team: Option<&'a str>,
+}
+
+fn print_vibe_response(response: &VibeResponse, banner: Option<&Font>) {
     println!();
-    println!("{}", "╭─────────────────────────────────────────╮".cyan());
-    println!("{}", "│              VIBE REPORT               │".cyan());
-    println!("{}", "╰─────────────────────────────────────────╯".cyan());
+    print_mood_header("VIBE REPORT", response, banner);
     println!();
-    
-    println!("🎨 {}: {}", "Theme".yellow(), response.theme.green());
-    println!("🎵 {}: {}", "Music".yellow(), response.music.green());
-    println!("⚡ {}: {}", "Stack".yellow(), response.stack.green());
-    println!("💭 {}: {}", "Motto".yellow(), response.motto.green());
+    print_report_lines(response);
     println!();
 }
 
-fn get_available_moods() -> Vec<&'static str> {
-    vec!["focus", "chaotic", "sadboi", "energetic", "chill", "creative", "productive", "nostalgic", "adventurous", "zen"]
-}
-
-fn get_ascii_art(mood: &str) -> Option<&'static str> {
-    match mood.to_lowercase().as_str() {
-        "focus" => Some(r#"
-   (  )   (   )  )
-    ) (   )  (  (
-    ( )  (    ) )
-    _____________
-   <__Focus!!!__>
-    -------------
-        \
-         \
-            .--.
-           |o_o |
-           |:_/ |
-          //   \ \
-         (|     | )
-        /'\_   _/`\
-        \___)=(___/
-"#),
-        "chaotic" => Some(r#"
-      .-"""-.
-     / .===. \
-     \/ 6 6 \/
-     ( \___/ )
- ___ooo__V__ooo___
-|  CHAOS MODE!!!  |
- -----------------
-     \   ^__^
-      \  (oo)\_______
-         (__)\       )\/\
-             ||----w |
-             ||     ||
-"#),
-        "sadboi" => Some(r#"
-      .-''''-.
-     /        \
-    |  .--.  |
-    | (    ) |
-     \ '--' /
-      '-..-'
-   Sadboi Vibes
-   ~~~~~~~~~~~
-    (︶︹︺)
-"#),
-        "energetic" => Some(r#"
-    🔥 ENERGETIC 🔥
-     ⚡ ⚡ ⚡ ⚡ ⚡
-    /           \
-   |  ROCK ON!  |
-    \           /
-     ⚡ ⚡ ⚡ ⚡ ⚡
-    🔥 FIRE UP! 🔥
-"#),
-        "chill" => Some(r#"
-    🌿 CHILL 🌿
-     ~~~~~~~~~
-    /         \
-   |  Relax   |
-   |  & Code  |
-    \         /
-     ~~~~~~~~~
-    🌿 VIBES 🌿
-"#),
-        "creative" => Some(r#"
-    🎨 CREATIVE 🎨
-     🌈 🌈 🌈 🌈
-    /           \
-   |  Create   |
-   |  & Build  |
-    \           /
-     🌈 🌈 🌈 🌈
-    🎨 ARTIST 🎨
-"#),
-        "productive" => Some(r#"
-    💼 PRODUCTIVE 💼
-     ⚡ ⚡ ⚡ ⚡ ⚡
-    /             \
-   |  Get Stuff  |
-   |   Done!     |
-    \             /
-     ⚡ ⚡ ⚡ ⚡ ⚡
-    💼 EFFICIENT 💼
-"#),
-        "nostalgic" => Some(r#"
-    📼 NOSTALGIC 📼
-     🎵 🎵 🎵 🎵
-    /             \
-   |  Retro Vibes |
-   |  & Memories  |
-    \             /
-     🎵 🎵 🎵 🎵
-    📼 CLASSIC 📼
-"#),
-        "adventurous" => Some(r#"
-    🌌 ADVENTUROUS 🌌
-     ⭐ ⭐ ⭐ ⭐ ⭐
-    /               \
-   |  Explore New  |
-   |   Horizons!   |
-    \               /
-     ⭐ ⭐ ⭐ ⭐ ⭐
-    🌌 EXPLORER 🌌
-"#),
-        "zen" => Some(r#"
-    🧘 ZEN 🧘
-     ☯️ ☯️ ☯️ ☯️
-    /         \
-   |  Breathe |
-   |  & Code  |
-    \         /
-     ☯️ ☯️ ☯️ ☯️
-    🧘 PEACE 🧘
-"#),
-        _ => None,
+/// A header in the mood's palette, or painted with its effect when it has
+/// one. With a `banner` font the mood's name stands in for the header, unless
+/// it's too wide for the terminal.
+fn print_mood_header(title: &str, response: &VibeResponse, banner: Option<&Font>) {
+    if let Some(big) = banner.and_then(|font| font.render_wrapped(&response.mood, panel::terminal_width())) {
+        for line in effects::render_lines(&big, response.effect, &response.gradient, 0.0) {
+            println!("{}", line);
+        }
+        return;
     }
+    if response.effect == Effect::Flat {
+        panel::print_header(title, Colors::Palette(response.palette));
+        return;
+    }
+    for line in effects::render_lines(&panel::frame(title), response.effect, &response.gradient, 0.0) {
+        println!("{}", line);
+    }
+}
+
+/// Theme (with swatches of its palette), music, stack and motto, wrapped to
+/// the terminal and labelled in the accent color. The motto gets the mood's
+/// effect.
+fn print_report_lines(response: &VibeResponse) {
+    let colors = Colors::Palette(response.palette);
+    let width = panel::terminal_width();
+    let mut theme = panel::field("🎨", "Theme", &response.theme, colors, width);
+    let swatches = response.palette.swatches();
+    let last = theme.last_mut().expect("a field has at least one line");
+    if panel::width(last) + 2 + panel::width(&swatches) <= width {
+        last.push_str(&format!("  {}", swatches));
+    } else {
+        theme.push(format!("{}{}", " ".repeat(panel::width("🎨 Theme: ")), swatches));
+    }
+    for line in theme {
+        println!("{}", line);
+    }
+    panel::print_field("🎵", "Music", &response.music, colors);
+    panel::print_field("⚡", "Stack", &response.stack, colors);
+    let motto = panel::field_with("💭", "Motto", &response.motto, colors, width, |parts| {
+        effects::render_lines(parts, response.effect, &response.gradient, 0.0)
+    });
+    for line in motto {
+        println!("{}", line);
+    }
+}
+
+/// Art with the mood's effect, animated if `--animate` asked. False if the
+/// mood has none.
+fn print_art(registry: &MoodRegistry, mood: &str) -> bool {
+    let Some(found) = registry.get(mood) else { return false };
+    let Some(art) = &found.ascii_art else { return false };
+    effects::show(art, found.effect, &found.gradient_stops());
+    true
+}
+
+fn get_available_moods(registry: &MoodRegistry) -> Vec<&str> {
+    registry.names()
+}
+
+fn get_ascii_art<'a>(registry: &'a MoodRegistry, mood: &str) -> Option<&'a str> {
+    registry.get(mood)?.ascii_art.as_deref()
 }
 
 fn print_godmode() {
-    let lines = [
-        "\x1b[92mInitializing GODMODE...\x1b[0m",
-        "\x1b[91mBypassing mood matrix...\x1b[0m",
-        "\x1b[96mInjecting quantum vibes...\x1b[0m",
-        "\x1b[93mEstablishing neural uplink...\x1b[0m",
-        "\x1b[95mSpawning 1337 threads...\x1b[0m",
-        "\x1b[92m\n╔══════════════════════════════════════════════╗\x1b[0m",
-        "\x1b[92m║           🦾 ELITE HACKER VIBE 🦾           ║\x1b[0m",
-        "\x1b[92m╚══════════════════════════════════════════════╝\x1b[0m",
-        "\x1b[96m🎨 Theme: Matrix Green on Black\x1b[0m",
-        "\x1b[92m🎵 Music: Glitchcore, synthwave, modem noise\x1b[0m",
-        "\x1b[93m⚡ Stack: Rust, Assembly, Brainfuck, Quantum APIs\x1b[0m",
-        "\x1b[95m💭 Motto: 'There is no spoon. Only root.'\x1b[0m",
-        "\x1b[92m\n[ACCESS GRANTED] Welcome, root overlord.\x1b[0m",
-        "\x1b[92m$ sudo rm -rf / --no-preserve-root\x1b[0m",
-        "\x1b[91m(Just kidding. Or am I?)\x1b[0m",
-    ];
+    let mut lines: Vec<String> = [
+        "Initializing GODMODE...".bright_green(),
+        "Bypassing mood matrix...".bright_red(),
+        "Injecting quantum vibes...".bright_cyan(),
+        "Establishing neural uplink...".bright_yellow(),
+        "Spawning 1337 threads...".bright_magenta(),
+    ]
+    .map(|line| line.to_string())
+    .to_vec();
+    lines.push(String::new());
+    lines.extend(panel::header("🦾 ELITE HACKER VIBE 🦾", Colors::Named(Color::BrightGreen)));
+    lines.extend([
+        "🎨 Theme: Matrix Green on Black".bright_cyan(),
+        "🎵 Music: Glitchcore, synthwave, modem noise".bright_green(),
+        "⚡ Stack: Rust, Assembly, Brainfuck, Quantum APIs".bright_yellow(),
+        "💭 Motto: 'There is no spoon. Only root.'".bright_magenta(),
+        "\n[ACCESS GRANTED] Welcome, root overlord.".bright_green(),
+        "$ sudo rm -rf / --no-preserve-root".bright_green(),
+        "(Just kidding. Or am I?)".bright_red(),
+    ]
+    .map(|line| line.to_string()));
     for line in lines.iter() {
         println!("{}", line);
-        thread::sleep(Duration::from_millis(400));
+        dramatic_pause(400);
     }
 }
 
-fn pomodoro_timer(minutes: u32, mood: &str) {
-    let total_seconds = minutes * 60;
-    let mut remaining = total_seconds;
-    
+/// Sleep for effect, but only when someone is watching: piped or redirected
+/// output gets everything at once.
+fn dramatic_pause(millis: u64) {
+    if io::stdout().is_terminal() {
+        thread::sleep(Duration::from_millis(millis));
+    }
+}
+
+/// Run the session and record it. Returns the mood and history id of a
+/// session that ran to the end, for [`ask_rating`].
+fn pomodoro_timer(registry: &MoodRegistry, plan: &Plan, mood: &str, banner: Option<&Font>) -> Option<(String, u64)> {
+    let started_at = Local::now();
+
     // Get the full vibe response for the mood
-    if let Some(response) = get_vibe_response(mood) {
-        println!("\n{}", "╭─────────────────────────────────────────╮".cyan());
-        println!("{}", "│            POMODORO VIBE               │".cyan());
-        println!("{}", "╰─────────────────────────────────────────╯".cyan());
+    if let Some(response) = get_vibe_response(registry, mood) {
+        println!();
+        print_mood_header("POMODORO VIBE", &response, banner);
         println!();
         
         // Show the vibe theme
-        println!("🎨 {}: {}", "Theme".yellow(), response.theme.green());
-        println!("🎵 {}: {}", "Music".yellow(), response.music.green());
-        println!("⚡ {}: {}", "Stack".yellow(), response.stack.green());
-        println!("💭 {}: {}", "Motto".yellow(), response.motto.green());
+        print_report_lines(&response);
         println!();
         
         // Show ASCII art
-        if let Some(ascii) = get_ascii_art(mood) {
-            println!("{}", ascii.cyan());
+        if print_art(registry, mood) {
             println!();
         }
     }
     
     // Timer message based on mood
-    let (emoji, msg) = match mood.to_lowercase().as_str() {
-        "focus" => ("🔵", "Stay focused!"),
-        "chaotic" => ("⚡", "Embrace the chaos!"),
-        "sadboi" => ("💜", "Code through the feels!"),
-        "energetic" => ("🔥", "Keep the energy up!"),
-        "chill" => ("🌿", "Stay chill and code on!"),
-        "creative" => ("🎨", "Let your creativity flow!"),
-        "productive" => ("💼", "Productivity mode: ON!"),
-        "nostalgic" => ("📼", "Old school grind!"),
-        "adventurous" => ("🌌", "Explore new code worlds!"),
-        "zen" => ("🧘", "Breathe and code."),
-        _ => ("⏳", "Time to vibe!"),
+    let known = registry.get(mood);
+    let emoji = known.map_or(moods::DEFAULT_TIMER_EMOJI, |m| m.timer_emoji.as_str());
+    let msg = known.map_or(moods::DEFAULT_TIMER_MESSAGE, |m| m.timer_message.as_str());
+    let break_art = match known {
+        Some(m) => m.paint(&m.break_art),
+        None => moods::DEFAULT_BREAK_ART.white().to_string(),
     };
+    // The countdown is drawn in the banner's font, or the default one
+    let default_font = banner.is_none().then(|| figlet::load(figlet::DEFAULT_FONT).ok()).flatten();
+    let big_digits = banner.or(default_font.as_ref()).map(|font| {
+        move |time: &str| match known {
+            Some(m) => effects::render_lines(&font.render(time), m.effect, &m.gradient_stops(), 0.0),
+            None => font.render(time),
+        }
+    });
+    let big_digits = big_digits.as_ref().map(|render| render as pomodoro::BigDigits);
     
-    println!("{} Pomodoro Timer: {} minutes | {}", emoji, minutes, msg);
-    println!("{}", "Press Ctrl+C to stop early".dimmed());
-    println!();
-    
-    // Countdown timer
-    while remaining > 0 {
-        let mins = remaining / 60;
-        let secs = remaining % 60;
-        print!("\r{} {:02}:{:02} remaining... ", emoji, mins, secs);
-        io::stdout().flush().unwrap();
-        thread::sleep(Duration::from_secs(1));
-        remaining -= 1;
+    println!("{} Pomodoro Timer: {} minutes | {}", emoji, plan.work_minutes, msg);
+    if plan.cycles > 1 {
+        println!(
+            "🔁 {} pomodoros · {} min short breaks · {} min long break every {}",
+            plan.cycles, plan.short_break_minutes, plan.long_break_minutes, plan.long_break_every
+        );
+    }
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        println!("{}", "Press Ctrl+C to stop early".dimmed());
     }
-    
-    println!("\r{} 00:00 Time's up! Take a break!           ", emoji);
     println!();
     
+    let mut summary = pomodoro::Summary::default();
+    let mut quit = false;
+    for cycle in 1..=plan.cycles {
+        if plan.cycles > 1 {
+            println!("{}", format!("🍅 Pomodoro {}/{}", cycle, plan.cycles).bold());
+        }
+        let work = pomodoro::countdown(u64::from(plan.work_minutes) * 60, emoji, "remaining", big_digits);
+        match work.outcome {
+            Outcome::Quit => {
+                println!("\r{} {}                    ", emoji, "Session stopped early".yellow());
+                println!();
+                summary.focused_seconds += work.elapsed_seconds;
+                quit = true;
+                break;
+            }
+            Outcome::Skipped => {
+                println!("\r{} ⏭️  Skipping ahead to the break           ", emoji);
+            }
+            Outcome::Finished => {
+                println!("\r{} 00:00 Time's up! Take a break!           ", emoji);
+            }
+        }
+        summary.record(Phase::Work, work);
+        println!();
+
+        let phase = plan.break_after(cycle);
+        print_break_screen(phase, plan.minutes(phase), &break_art);
+
+        // After the last pomodoro the session is over, so there is nothing
+        // to count down to
+        if cycle < plan.cycles {
+            let rest = pomodoro::countdown(u64::from(plan.minutes(phase)) * 60, emoji, "of break left", big_digits);
+            summary.record(phase, rest);
+            if rest.outcome == Outcome::Quit {
+                println!("\r{} {}                    ", emoji, "Session stopped early".yellow());
+                println!();
+                quit = true;
+                break;
+            }
+            println!("\r{} 00:00 Break's over, back to it!           ", emoji);
+            println!();
+        }
+    }
+
+    if plan.cycles > 1 {
+        print_pomodoro_summary(&summary, plan);
+    }
+
+    let name = known.map_or(mood, |m| m.name.as_str());
+    let id = record_history(
+        registry,
+        name,
+        Event::Pomodoro {
+            started_at,
+            ended_at: Local::now(),
+            planned_minutes: plan.work_minutes * plan.cycles,
+            actual_minutes: (summary.focused_seconds as f64 / 60.0 * 100.0).round() / 100.0,
+            planned_pomodoros: plan.cycles,
+            completed_pomodoros: summary.completed_pomodoros,
+            skipped_pomodoros: summary.skipped_pomodoros,
+            completed: !quit && summary.skipped_pomodoros == 0,
+        },
+    );
+    id.filter(|_| !quit).map(|id| (name.to_string(), id))
+}
+
+/// Offer to rate a finished session right away, when someone is there to
+/// answer. An empty answer skips it.
+fn ask_rating(registry: &MoodRegistry, mood: &str, id: u64) {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return;
+    }
+    print!("⭐ How was it? Rate 1-5 or up/down (Enter to skip): ");
+    let _ = io::Write::flush(&mut io::stdout());
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() || answer.trim().is_empty() {
+        return;
+    }
+    match prefs::parse_score(&answer) {
+        Ok(score) => save_rating(registry, mood, id, score),
+        Err(err) => println!("{}", format!("{}, skipped. Run `vibe rate` to try again", err).dimmed()),
+    }
+}
+
+fn print_break_screen(phase: Phase, minutes: u32, break_art: &str) {
     // Break message with mood-specific ASCII
-    println!("{}", "╭─────────────────────────────────────────╮".green());
-    println!("{}", "│              BREAK TIME!               │".green());
-    println!("{}", "╰─────────────────────────────────────────╯".green());
+    if phase == Phase::LongBreak {
+        panel::print_header("LONG BREAK!", Colors::Named(Color::Magenta));
+    } else {
+        panel::print_header("BREAK TIME!", Colors::Named(Color::Green));
+    }
     println!();
     
-    println!("{}\n", match mood.to_lowercase().as_str() {
-        "focus" => "  (  )   (   )  )\n   ) (   )  (  (\n   ( )  (    ) )\n   _____________\n  <__Break!!!__>\n   -------------\n       \\\n        \\\n           .--.\n          |o_o |\n          |:_/ |\n         //   \\ \\\n        (|     | )\n       /'\\_   _/`\\\n       \\___)=(___/",
-        "chaotic" => "      .-\"\"\"-.\n     / .===. \\\n     \\/ 6 6 \\/\n     ( \\___/ )\n ___ooo__V__ooo___\n|  BREAK CHAOS!  |\n -----------------",
-        "sadboi" => "      .-''''-.\n     /        \\\n    |  .--.  |\n    | (    ) |\n     \\ '--' /\n      '-..-'\n   Break time, friend\n   ~~~~~~~~~~~~~~\n    (︶︹︺)",
-        "energetic" => "    🔥 BREAK 🔥\n     ⚡ ⚡ ⚡ ⚡ ⚡\n    /           \\\n   |  Recharge  |\n   |  & Return  |\n    \\           /\n     ⚡ ⚡ ⚡ ⚡ ⚡\n    🔥 STRONGER 🔥",
-        "chill" => "    🌿 BREAK 🌿\n     ~~~~~~~~~\n    /         \\\n   |  Relax   |\n   |  & Reset  |\n    \\         /\n     ~~~~~~~~~\n    🌿 RENEWED 🌿",
-        "creative" => "    🎨 BREAK 🎨\n     🌈 🌈 🌈 🌈\n    /           \\\n   |  Refresh  |\n   |  & Create  |\n    \\           /\n     🌈 🌈 🌈 🌈\n    🎨 INSPIRED 🎨",
-        "productive" => "    💼 BREAK 💼\n     ⚡ ⚡ ⚡ ⚡ ⚡\n    /             \\\n   |  Rest &    |\n   |  Recharge  |\n    \\             /\n     ⚡ ⚡ ⚡ ⚡ ⚡\n    💼 READY 💼",
-        "nostalgic" => "    📼 BREAK 📼\n     🎵 🎵 🎵 🎵\n    /             \\\n   |  Remember  |\n   |  & Reflect  |\n    \\             /\n     🎵 🎵 🎵 🎵\n    📼 WISER 📼",
-        "adventurous" => "    🌌 BREAK 🌌\n     ⭐ ⭐ ⭐ ⭐ ⭐\n    /               \\\n   |  Rest &      |\n   |  Prepare     |\n    \\               /\n     ⭐ ⭐ ⭐ ⭐ ⭐\n    🌌 READY 🌌",
-        "zen" => "    🧘 BREAK 🧘\n     ☯️ ☯️ ☯️ ☯️\n    /         \\\n   |  Breathe |\n   |  & Reset  |\n    \\         /\n     ☯️ ☯️ ☯️ ☯️\n    🧘 CENTERED 🧘",
-        _ => "\n  (•_•)  ( •_•)>⌐■-■  (⌐■_■)\nBreak like a boss!",
-    });
+    println!("{}\n", break_art);
+    let tip = match phase {
+        Phase::LongBreak => "Step away properly: stretch, hydrate, get some air.",
+        _ => "Stand up, look at something far away, breathe.",
+    };
+    let label = format!("{} min:", minutes);
+    let indent = panel::width(&label) + 1;
+    for (n, line) in panel::wrap(tip, panel::terminal_width() - indent).iter().enumerate() {
+        let lead = if n == 0 { label.bold().to_string() } else { " ".repeat(indent - 1) };
+        println!("{} {}", lead, line.dimmed());
+    }
+    println!();
+}
+
+fn print_pomodoro_summary(summary: &pomodoro::Summary, plan: &Plan) {
+    panel::print_header("SESSION SUMMARY", Colors::Named(Color::Cyan));
+    println!();
+    println!(
+        "🍅 {}: {}/{}{}",
+        "Pomodoros".yellow(),
+        summary.completed_pomodoros.to_string().green(),
+        plan.cycles,
+        match summary.skipped_pomodoros {
+            0 => String::new(),
+            skipped => format!(" ({} skipped)", skipped).dimmed().to_string(),
+        }
+    );
+    println!(
+        "⏱️  {}: {}",
+        "Focused".yellow(),
+        pomodoro::format_duration(summary.focused_seconds).green()
+    );
+    println!(
+        "☕ {}: {} short, {} long ({})",
+        "Breaks".yellow(),
+        summary.short_breaks.to_string().green(),
+        summary.long_breaks.to_string().green(),
+        pomodoro::format_duration(summary.break_seconds)
+    );
+    println!();
 }
 
 fn honeypot_response() {
     println!("\n{}", "🔐 SECRET MODE ACTIVATED 🔐".red().bold());
     println!("{}", "Accessing classified vibe database...".yellow());
-    thread::sleep(Duration::from_millis(800));
+    dramatic_pause(800);
     println!("{}", "Bypassing security protocols...".yellow());
-    thread::sleep(Duration::from_millis(600));
+    dramatic_pause(600);
     println!("{}", "Decrypting quantum vibe matrix...".yellow());
-    thread::sleep(Duration::from_millis(700));
+    dramatic_pause(700);
     println!();
-    println!("{}", "╭─────────────────────────────────────────╮".red());
-    println!("{}", "│           CLASSIFIED VIBES             │".red());
-    println!("{}", "╰─────────────────────────────────────────╯".red());
+    panel::print_header("CLASSIFIED VIBES", Colors::Named(Color::Red));
     println!();
-    println!("🎨 Theme: Invisible Spectrum");
-    println!("🎵 Music: Government surveillance frequencies");
-    println!("⚡ Stack: Quantum computers, classified APIs, black ops tools");
-    println!("💭 Motto: 'The best vibes are the ones you can't see'");
+    let colors = Colors::Named(Color::Red);
+    panel::print_field("🎨", "Theme", "Invisible Spectrum", colors);
+    panel::print_field("🎵", "Music", "Government surveillance frequencies", colors);
+    panel::print_field("⚡", "Stack", "Quantum computers, classified APIs, black ops tools", colors);
+    panel::print_field("💭", "Motto", "'The best vibes are the ones you can't see'", colors);
     println!();
     println!("{}", "⚠️  WARNING: This mode is completely fake!".green());
     println!("{}", "   You've been honeypotted! 🕷️".green());
@@ -387,11 +689,11 @@ fn honeypot_response() {
 fn debug_honeypot() {
     println!("\n{}", "🐛 DEBUG MODE ENABLED 🐛".cyan().bold());
     println!("{}", "Loading verbose output...".yellow());
-    thread::sleep(Duration::from_millis(500));
+    dramatic_pause(500);
     println!("{}", "Analyzing vibe quantum states...".yellow());
-    thread::sleep(Duration::from_millis(400));
+    dramatic_pause(400);
     println!("{}", "Calculating mood entropy...".yellow());
-    thread::sleep(Duration::from_millis(300));
+    dramatic_pause(300);
     println!();
     println!("{}", "DEBUG INFO:".cyan().bold());
     println!("  - Vibe entropy: 42.1337");
@@ -404,124 +706,677 @@ fn debug_honeypot() {
 fn api_honeypot(api_key: &str) {
     println!("\n{}", "☁️  CLOUD SYNC INITIALIZED ☁️".blue().bold());
     println!("{}", "Connecting to vibe cloud...".yellow());
-    thread::sleep(Duration::from_millis(600));
+    dramatic_pause(600);
     println!("{}", "Authenticating with API key...".yellow());
-    thread::sleep(Duration::from_millis(500));
+    dramatic_pause(500);
     println!("{}", "Syncing your vibes to the cloud...".yellow());
-    thread::sleep(Duration::from_millis(400));
+    dramatic_pause(400);
     println!();
     println!("API Key: {}...***", &api_key[..8]);
-    println!("{}", "Status: Connected to fake cloud service");
-    println!("{}", "Sync: Your vibes are now stored in the void");
-    println!("{}", "Security: This is completely made up!");
+    println!("Status: Connected to fake cloud service");
+    println!("Sync: Your vibes are now stored in the void");
+    println!("Security: This is completely made up!");
     println!();
 }
 
-fn interactive_mode() {
-    let moods = get_available_moods();
-    let mut selected_index = 0;
-    
-    // Simple interactive mode without alternate screen
-    loop {
-        // Clear screen using simple method
-        print!("\x1B[2J\x1B[1;1H"); // ANSI clear screen
-        io::stdout().flush().unwrap();
-        
-        // Print header
-        println!("{}", "╭─────────────────────────────────────────────────────────╮".cyan());
-        println!("{}", "│                🎵 VIBE SELECTOR 🎵                   │".cyan());
-        println!("{}", "╰─────────────────────────────────────────────────────────╯".cyan());
-        println!();
-        println!("{}", "Use ↑↓ arrows to navigate, Enter to select, Ctrl+C to exit".yellow());
-        println!();
-        
-        // Print mood list
-        for (i, mood) in moods.iter().enumerate() {
-            if i == selected_index {
-                print!("{}", "  ▶ ".green());
-                println!("{}", mood.to_uppercase().green().bold());
-            } else {
-                print!("    ");
-                println!("{}", mood);
+fn interactive_mode(
+    registry: &MoodRegistry,
+    picker: fn(&MoodRegistry) -> io::Result<Option<String>>,
+    banner: Option<&Font>,
+) {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("{}", "❌ Interactive mode needs a terminal".red());
+        std::process::exit(1);
+    }
+
+    let selected_mood = match picker(registry) {
+        Ok(Some(mood)) => mood,
+        Ok(None) => {
+            println!("{}", "👋 Vibe selection cancelled".yellow());
+            return;
+        }
+        Err(err) => {
+            eprintln!("{} {}", "❌ Interactive mode failed:".red(), err);
+            std::process::exit(1);
+        }
+    };
+
+    // Print final selection
+    println!("{}", "🎉 Selected vibe:".green().bold());
+    if let Some(response) = get_vibe_response(registry, &selected_mood) {
+        println!("{}", palette::bold(&selected_mood.to_uppercase(), response.palette.primary));
+
+        // Print full vibe response
+        print_vibe_response(&response, banner);
+        record_history(registry, &selected_mood, Event::Pick { at: Local::now() });
+    }
+
+    // Print ASCII art
+    print_art(registry, &selected_mood);
+}
+
+/// Block the mood's distracting sites, exiting on failure since the user
+/// explicitly asked for focus mode.
+fn start_focus_mode(registry: &MoodRegistry, mood: Option<&str>, hosts: &Path) {
+    let sites: Vec<String> = match mood.and_then(|name| registry.get(name)) {
+        Some(mood) => mood.blocked_sites.clone(),
+        None => moods::DEFAULT_BLOCKED_SITES.iter().map(|s| s.to_string()).collect(),
+    };
+
+    match focus::block(hosts, &sites) {
+        Ok(blocked) => {
+            println!("🚫 {} blocked {} host names in {}", "Focus mode ON:".green().bold(), blocked.names, hosts.display());
+            if let Some(backup) = blocked.backup {
+                println!("{}", format!("   Backup saved to {}", backup.display()).dimmed());
             }
         }
-        
-        println!();
-        println!("{}", "╭─────────────────────────────────────────────────────────╮".blue());
-        println!("{}", "│                     PREVIEW                           │".blue());
-        println!("{}", "╰─────────────────────────────────────────────────────────╯".blue());
-        println!();
-        
-        // Show preview of selected mood
-        let selected_mood = moods[selected_index];
-        if let Some(response) = get_vibe_response(selected_mood) {
-            println!("🎨 {}: {}", "Theme".yellow(), response.theme.green());
-            println!("🎵 {}: {}", "Music".yellow(), response.music.green());
-            println!("⚡ {}: {}", "Stack".yellow(), response.stack.green());
-            println!("💭 {}: {}", "Motto".yellow(), response.motto.green());
+        Err(err) => {
+            eprintln!("{} {}", "❌ Focus mode failed:".red(), err);
+            std::process::exit(1);
         }
-        
-        // Show ASCII art preview
-        if let Some(ascii) = get_ascii_art(selected_mood) {
-            println!();
-            println!("{}", ascii.cyan());
+    }
+}
+
+fn end_focus_mode(hosts: &Path) {
+    match focus::unblock(hosts) {
+        Ok(true) => println!("✅ {} sites unblocked in {}", "Focus mode OFF:".green().bold(), hosts.display()),
+        Ok(false) => println!("{}", "Focus mode was not active, nothing to unblock".dimmed()),
+        Err(err) => {
+            eprintln!("{} {}", "❌ Unblock failed:".red(), err);
+            std::process::exit(1);
         }
-        
-        // Simple input handling
-        let mut input = String::new();
-        print!("{}", "Enter choice (↑/↓/Enter/q): ".yellow());
-        io::stdout().flush().unwrap();
-        
-        if io::stdin().read_line(&mut input).is_ok() {
-            match input.trim() {
-                "w" | "k" | "up" => {
-                    if selected_index > 0 {
-                        selected_index -= 1;
-                    }
-                }
-                "s" | "j" | "down" => {
-                    if selected_index < moods.len() - 1 {
-                        selected_index += 1;
-                    }
-                }
-                "" | "enter" => {
-                    // Print final selection
-                    let selected_mood = moods[selected_index];
-                    println!("\n{}", "🎉 Selected vibe:".green().bold());
-                    println!("{}", selected_mood.to_uppercase().cyan().bold());
-                    println!();
-                    
-                    // Print full vibe response
-                    if let Some(response) = get_vibe_response(selected_mood) {
-                        print_vibe_response(&response);
-                    }
-                    
-                    // Print ASCII art
-                    if let Some(ascii) = get_ascii_art(selected_mood) {
-                        println!("{}", ascii.cyan());
-                    }
-                    
-                    return;
+    }
+}
+
+fn setup_workspace(registry: &MoodRegistry, mood: &str, dry_run: bool) {
+    let mood = registry.get(mood).expect("mood was resolved against the registry");
+    let dir = std::env::current_dir().expect("Failed to read current directory");
+    let changes = workspace::plan(&dir, mood).unwrap_or_else(|err| {
+        eprintln!("{} {}", "❌ Workspace setup failed:".red(), err);
+        std::process::exit(1);
+    });
+
+    if dry_run {
+        for change in &changes {
+            let path = change.path.strip_prefix(&dir).unwrap_or(&change.path);
+            if change.is_unchanged() {
+                println!("{}", format!("  {} (unchanged)", path.display()).dimmed());
+                continue;
+            }
+            if let Some(backup) = &change.backup {
+                let backup = backup.strip_prefix(&dir).unwrap_or(backup);
+                println!("{}", format!("  {} has comments; the original would be kept as {}", path.display(), backup.display()).yellow());
+            }
+            println!("{}", format!("--- a/{}", path.display()).bold());
+            println!("{}", format!("+++ b/{}", path.display()).bold());
+            for (tag, line) in workspace::diff_lines(&change.before, &change.after) {
+                match tag {
+                    '-' => println!("{}", format!("-{}", line).red()),
+                    '+' => println!("{}", format!("+{}", line).green()),
+                    _ => println!(" {}", line),
                 }
-                "q" | "quit" | "exit" => {
-                    println!("\n{}", "👋 Vibe selection cancelled".yellow());
-                    return;
+            }
+        }
+        return;
+    }
+
+    if let Err(err) = workspace::apply(&changes) {
+        eprintln!("{} {}", "❌ Workspace setup failed:".red(), err);
+        std::process::exit(1);
+    }
+    println!("🛠️  {} VS Code workspace tuned for {}", "Done:".green().bold(), mood.name.cyan());
+    println!("🎨 {}: {}", "Color theme".yellow(), mood.vscode_theme.green());
+    for change in &changes {
+        let path = change.path.strip_prefix(&dir).unwrap_or(&change.path);
+        let status = if change.is_unchanged() { "unchanged" } else { "updated" };
+        println!("   {} {}", path.display(), format!("({})", status).dimmed());
+        if let Some(backup) = change.backup.as_ref().filter(|_| !change.is_unchanged()) {
+            let backup = backup.strip_prefix(&dir).unwrap_or(backup);
+            println!("{} {}", "⚠️  Its comments couldn't be kept, the original is in".yellow(), backup.display());
+        }
+    }
+}
+
+/// Put each mood's motto in its character's mouth (see
+/// [`MoodRegistry::say_mottos`]), wrapped to fit the terminal.
+fn say_mottos(registry: &mut MoodRegistry, cow: Option<&Cow>, bubble: Option<Bubble>) {
+    for err in registry.say_mottos(cow, bubble, bubble_width()) {
+        eprintln!("{} {}", "⚠️  Ignoring cow for".yellow(), err);
+    }
+}
+
+/// How wide a character's bubble may be, leaving room for its border.
+fn bubble_width() -> usize {
+    cowsay::BUBBLE_WIDTH.min(panel::terminal_width().saturating_sub(4))
+}
+
+fn load_registry() -> MoodRegistry {
+    let mut registry = MoodRegistry::load().unwrap_or_else(|err| {
+        eprintln!("{} {}", "⚠️  Ignoring custom moods:".yellow(), err);
+        MoodRegistry::builtin()
+    });
+    match ArtPack::load() {
+        Ok(pack) => {
+            for skipped in &pack.skipped {
+                eprintln!("{} {}", "⚠️  Ignoring art file:".yellow(), skipped);
+            }
+            registry.add_art(&pack);
+        }
+        Err(err) => eprintln!("{} {}", "⚠️  Ignoring art files:".yellow(), err),
+    }
+    registry
+}
+
+/// History is a nice-to-have, so failing to write it only earns a warning.
+/// Returns the new entry's id.
+fn record_history(registry: &MoodRegistry, mood: &str, event: Event) -> Option<u64> {
+    let variant = registry.get(mood).and_then(|m| m.variant);
+    history::record(mood, variant, event)
+        .map_err(|err| eprintln!("{} {}", "⚠️  Could not save history:".yellow(), err))
+        .ok()
+}
+
+fn save_rating(registry: &MoodRegistry, mood: &str, id: u64, score: u8) {
+    let event = Event::Rating { at: Local::now(), entry: id, score };
+    if record_history(registry, mood, event).is_some() {
+        println!("{} Rated #{} ({}) {}", "⭐".yellow(), id, mood, prefs::stars(score).yellow());
+    }
+}
+
+/// `vibe rate`: score the latest pick or session, or the one with `id`.
+fn run_rate(score: u8, id: Option<u64>) {
+    let entries = history::load().unwrap_or_else(|err| {
+        eprintln!("{} {}", "❌ Could not read history:".red(), err);
+        std::process::exit(1);
+    });
+    let rateable = |entry: &&history::Entry| !matches!(entry.event, Event::Rating { .. });
+    let target = match id {
+        Some(id) => entries.iter().find(|e| e.id == id),
+        None => entries.iter().rev().find(rateable),
+    };
+    let Some(target) = target.filter(rateable) else {
+        let message = match id {
+            Some(id) => format!("No pick or pomodoro with id {} in the history", id),
+            None => "Nothing to rate yet. Pick a vibe or start a --timer first".to_string(),
+        };
+        eprintln!("{} {}", "❌".red(), message.red());
+        std::process::exit(1);
+    };
+
+    let event = Event::Rating { at: Local::now(), entry: target.id, score };
+    if let Err(err) = history::record(&target.mood, target.variant, event) {
+        eprintln!("{} {}", "❌ Could not save the rating:".red(), err);
+        std::process::exit(1);
+    }
+    println!("{} Rated #{} ({}) {}", "⭐".yellow(), target.id, target.mood, prefs::stars(score).yellow());
+}
+
+/// `vibe prefs`: show what the ratings taught, or forget them.
+fn run_prefs(reset: bool, mood: Option<String>) {
+    if reset {
+        let mood = mood.map(|mood| history_mood(&load_registry(), &history::load().unwrap_or_default(), &mood));
+        let result = history::delete_where(|entry| {
+            matches!(entry.event, Event::Rating { .. }) && mood.as_ref().is_none_or(|m| entry.mood.eq_ignore_ascii_case(m))
+        });
+        match result {
+            Ok(count) => println!("🧹 Forgot {} {}", count, if count == 1 { "rating" } else { "ratings" }),
+            Err(err) => {
+                eprintln!("{} {}", "❌ Could not reset preferences:".red(), err);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    let entries = history::load().unwrap_or_else(|err| {
+        eprintln!("{} {}", "❌ Could not read history:".red(), err);
+        std::process::exit(1);
+    });
+    prefs::print_report(&Prefs::from_history(&entries), &load_registry());
+}
+
+/// The mood `input` names, alias or unique prefix included, exiting with
+/// suggestions if there isn't exactly one.
+fn lookup_mood<'a>(registry: &'a MoodRegistry, input: &str) -> &'a moods::Mood {
+    match registry.lookup(input) {
+        Lookup::Found(found) => found,
+        Lookup::Ambiguous(candidates) => {
+            let message = format!("'{}' could be any of: {}", input, candidates.join(", "));
+            mood_error(registry, input, "ambiguous_mood", message, candidates, None)
+        }
+        Lookup::Unknown(close) => {
+            mood_error(registry, input, "unknown_mood", format!("Unknown mood '{}'", input), close, None)
+        }
+    }
+}
+
+/// The mood name history entries were recorded under for `input`. Names in
+/// the history are taken as they are, since blends and moods since removed
+/// from `moods.toml` aren't in the registry; anything else is looked up.
+fn history_mood(registry: &MoodRegistry, entries: &[history::Entry], input: &str) -> String {
+    match entries.iter().find(|entry| entry.mood.eq_ignore_ascii_case(input.trim())) {
+        Some(entry) => entry.mood.clone(),
+        None => lookup_mood(registry, input).name.clone(),
+    }
+}
+
+fn run_art(action: ArtAction) {
+    let registry = load_registry();
+    // load_registry has already warned about a pack it couldn't read
+    let pack = ArtPack::load().unwrap_or_default();
+    match action {
+        ArtAction::List => print_art_list(&registry, &pack),
+        ArtAction::Show { mood, break_art } => {
+            print_art_variants(lookup_mood(&registry, &mood), &pack, break_art);
+        }
+    }
+}
+
+/// Where a mood's art comes from: its files, relative to the art directory,
+/// or nothing if it has its built-in or `moods.toml` art.
+fn art_sources(pack: &ArtPack, mood: &str, kind: Kind) -> Vec<String> {
+    let dir = art::art_dir().unwrap_or_default();
+    pack.for_mood(mood, kind)
+        .map(|file| file.path.strip_prefix(&dir).unwrap_or(&file.path).display().to_string())
+        .collect()
+}
+
+fn print_art_list(registry: &MoodRegistry, pack: &ArtPack) {
+    panel::print_header("MOOD ART", Colors::Named(Color::Cyan));
+    let width = registry.names().iter().map(|name| name.len()).max().unwrap_or(0);
+    let count = |n: usize, noun: &str| match n {
+        0 => format!("no {}", noun),
+        _ => format!("{} {}", n, noun),
+    };
+    for mood in registry.all() {
+        let mut notes = art_sources(pack, &mood.name, Kind::Art);
+        notes.extend(art_sources(pack, &mood.name, Kind::Break));
+        if let Some(cow) = &mood.cow {
+            notes.push(format!("{} says the motto", cow));
+        }
+        let line = format!(
+            "   {} {:<7} {:<14} {}",
+            format!("{:<width$}", mood.name, width = width).cyan(),
+            count(mood.variants.art.len(), "art"),
+            count(mood.variants.breaks.len(), "break art"),
+            notes.join(", ").dimmed()
+        );
+        println!("{}", line.trim_end());
+    }
+
+    for file in pack.files.iter().filter(|file| !registry.all().iter().any(|m| m.name == file.mood)) {
+        eprintln!("{} {}", format!("⚠️  No mood named '{}' for", file.mood).yellow(), file.path.display());
+    }
+    if let Some(dir) = art::art_dir() {
+        println!();
+        println!("{}", format!("Art files go in {}", dir.display()).dimmed());
+        println!("{}", "   <mood>.txt, <mood>.<variant>.txt, <mood>.break.txt, <mood>.break.<variant>.txt".dimmed());
+        println!("{}", "   or a folder of them per art pack".dimmed());
+    }
+}
+
+/// Each variant of a mood's art, colored as the report or timer shows it.
+fn print_art_variants(mood: &moods::Mood, pack: &ArtPack, break_art: bool) {
+    let (kind, variants, noun) = match break_art {
+        false => (Kind::Art, &mood.variants.art, "art"),
+        true => (Kind::Break, &mood.variants.breaks, "break art"),
+    };
+    let sources = art_sources(pack, &mood.name, kind);
+    if variants.is_empty() {
+        println!("{}", format!("{} has no {}", mood.name, noun).dimmed());
+    }
+    for (i, text) in variants.iter().enumerate() {
+        let mut label = format!("{} {} {}/{}", mood.name, noun, i + 1, variants.len());
+        if let Some(source) = sources.get(i) {
+            label = format!("{} · {}", label, source);
+        }
+        println!("{}", format!("── {} ──", label).dimmed());
+        println!("{}\n", mood.paint(text.trim_matches('\n')));
+    }
+
+    // A character saying the motto takes the art's place in the report
+    if let Some(name) = mood.cow.as_deref().filter(|_| !break_art) {
+        match cowsay::load(name) {
+            Ok(cow) => {
+                println!("{}", format!("── {} says the motto in place of the art ──", name).dimmed());
+                println!("{}", mood.paint(&cow.say(&mood.motto, mood.bubble, bubble_width())));
+            }
+            Err(err) => eprintln!("{} {}", "⚠️  Ignoring cow:".yellow(), err),
+        }
+    }
+}
+
+fn run_history(action: Option<HistoryAction>, filter: HistoryFilter, limit: Option<usize>) {
+    match action {
+        None => {
+            let entries = history::load().unwrap_or_else(|err| {
+                eprintln!("{} {}", "❌ Could not read history:".red(), err);
+                std::process::exit(1);
+            });
+            let filter = filter.into_filter(&load_registry(), &entries);
+            let matching: Vec<&history::Entry> = entries.iter().filter(|e| filter.matches(e)).collect();
+            if matching.is_empty() && !entries.is_empty() {
+                println!("{}", "No history entries match those filters".dimmed());
+                return;
+            }
+            let skip = limit.map_or(0, |n| matching.len().saturating_sub(n));
+            print_history(&matching[skip..]);
+        }
+        Some(HistoryAction::Delete { ids, filter, all }) => {
+            if ids.is_empty() && filter.is_empty() && !all {
+                eprintln!("{}", "❌ Nothing to delete: give entry ids, a filter, or --all".red());
+                std::process::exit(1);
+            }
+            let filter = filter.into_filter(&load_registry(), &history::load().unwrap_or_default());
+            let result = history::delete_where(|entry| {
+                (all || ids.is_empty() || ids.contains(&entry.id)) && filter.matches(entry)
+            });
+            match result {
+                Ok(count) => println!("🗑️  Deleted {} history {}", count, if count == 1 { "entry" } else { "entries" }),
+                Err(err) => {
+                    eprintln!("{} {}", "❌ Could not delete history:".red(), err);
+                    std::process::exit(1);
                 }
-                _ => {
-                    // Try to parse as number
-                    if let Ok(num) = input.trim().parse::<usize>() {
-                        if num > 0 && num <= moods.len() {
-                            selected_index = num - 1;
-                        }
-                    }
+            }
+        }
+    }
+}
+
+fn print_history(entries: &[&history::Entry]) {
+    if entries.is_empty() {
+        println!("{}", "No history yet. Pick a vibe or start a --timer!".dimmed());
+        return;
+    }
+    println!("{}", format!("{:>5}  {:<16}  {:<8}  {:<12}  {}", "ID", "WHEN", "KIND", "MOOD", "DETAILS").bold());
+    for entry in entries {
+        let details = match &entry.event {
+            Event::Pick { .. } => String::new(),
+            Event::Rating { entry, score, .. } => format!("{} for #{}", prefs::stars(*score).yellow(), entry),
+            Event::Pomodoro {
+                planned_minutes,
+                actual_minutes,
+                planned_pomodoros,
+                completed_pomodoros,
+                skipped_pomodoros,
+                completed,
+                ..
+            } => format!(
+                "{:.0}/{} min · {}/{} 🍅 · {}",
+                actual_minutes,
+                planned_minutes,
+                completed_pomodoros,
+                planned_pomodoros,
+                if *completed {
+                    "completed".green()
+                } else if completed_pomodoros + skipped_pomodoros == *planned_pomodoros {
+                    // Every pomodoro was started, so it wasn't quit
+                    format!("{} skipped", skipped_pomodoros).yellow()
+                } else {
+                    "aborted".red()
                 }
+            ),
+        };
+        println!(
+            "{:>5}  {:<16}  {:<8}  {}  {}",
+            entry.id,
+            entry.at().format("%Y-%m-%d %H:%M").to_string(),
+            entry.kind(),
+            format!("{:<12}", entry.mood).cyan(),
+            details
+        );
+    }
+}
+
+/// Turn what the user typed into a mood name: a name, alias or prefix, or
+/// failing that free text read by [`detect`]. Unknown or ambiguous input
+/// ends the program with suggestions, as structured output when `--format`
+/// was requested.
+fn resolve_mood(registry: &MoodRegistry, input: &str, format: Option<OutputFormat>, explain: bool) -> String {
+    let (code, message, suggestions) = match registry.lookup(input) {
+        Lookup::Found(mood) => {
+            if format.is_none() && !is_same_name(input, &mood.name) {
+                println!("{}", format!("🔀 '{}' → {}", input.trim(), mood.name).dimmed());
+            } else if format.is_none() && explain {
+                println!("{}", format!("🔍 '{}' is a mood name, nothing to explain", mood.name).dimmed());
             }
+            return mood.name.clone();
         }
+        Lookup::Ambiguous(candidates) => (
+            "ambiguous_mood",
+            format!("'{}' could be any of: {}", input, candidates.join(", ")),
+            candidates,
+        ),
+        Lookup::Unknown(close) => ("unknown_mood", format!("Unknown mood '{}'", input), close),
+    };
+
+    if let Some(detection) = detect::detect(registry, input) {
+        if format.is_none() {
+            println!("{}", format!("🔮 '{}' → {}", input.trim(), detection.mood).dimmed());
+            if explain {
+                detect::print_explanation(&detection);
+            }
+        }
+        return detection.mood;
+    }
+
+    mood_error(registry, input, code, message, suggestions, format)
+}
+
+/// Explain why `input` isn't a mood and exit, as a structured error when
+/// `--format` was given.
+fn mood_error(
+    registry: &MoodRegistry,
+    input: &str,
+    code: &str,
+    message: String,
+    suggestions: Vec<&str>,
+    format: Option<OutputFormat>,
+) -> ! {
+    if let Some(format) = format {
+        let error = output::ErrorOutput {
+            error: output::ErrorDetails {
+                code,
+                message,
+                mood: input,
+                suggestions,
+                available_moods: get_available_moods(registry),
+            },
+        };
+        println!("{}", output::render(&error, format));
+        std::process::exit(1);
+    }
+
+    eprintln!("{} {}", "❌".red(), message.red());
+    if code == "unknown_mood" && !suggestions.is_empty() {
+        let names: Vec<String> = suggestions.iter().map(|s| s.cyan().bold().to_string()).collect();
+        eprintln!("   Did you mean {}?", names.join(" or "));
     }
+    eprintln!();
+    eprintln!("{}", "Available moods:".yellow());
+    let width = get_available_moods(registry).iter().map(|m| m.len()).max().unwrap_or(0);
+    for mood in registry.all() {
+        eprintln!("  {}  {}", format!("{:<width$}", mood.name, width = width).cyan(), mood.description.dimmed());
+    }
+    std::process::exit(1);
+}
+
+/// Resolve each part of a blend like "focus:2+zen", add the merged mood to
+/// the registry and return its name.
+fn add_blend(registry: &mut MoodRegistry, input: &str, format: Option<OutputFormat>, rng: &mut impl Rng) -> String {
+    let parts = blend::parse(input)
+        .unwrap_or_else(|message| mood_error(registry, input, "invalid_blend", message, Vec::new(), format));
+
+    // Resolving may print "'foc' → focus" lines; repeated moods add up
+    let mut weights: Vec<(String, f64)> = Vec::new();
+    for (part, weight) in parts {
+        let name = resolve_mood(registry, part, format, false);
+        match weights.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, total)) => *total += weight,
+            None => weights.push((name, weight)),
+        }
+    }
+    if let [(name, _)] = weights.as_slice() {
+        return name.clone();
+    }
+
+    let parts: Vec<(&moods::Mood, f64)> = weights
+        .iter()
+        .map(|(name, weight)| (registry.get(name).expect("resolved against the registry"), *weight))
+        .collect();
+    let mood = blend::blend(&parts, rng);
+    if format.is_none() {
+        println!("{}", format!("🧪 {}", mood.description).dimmed());
+    }
+    let name = mood.name.clone();
+    registry.add(mood);
+    name
+}
+
+/// The mood asked for on the command line, by name, free text or
+/// `--energy`/`--positivity`. `None` means pick one at random.
+fn requested_mood(registry: &MoodRegistry, cli: &Cli, format: Option<OutputFormat>) -> Option<String> {
+    if let (Some(energy), Some(positivity)) = (cli.energy, cli.positivity) {
+        let mood = registry.nearest(energy, positivity);
+        if format.is_none() {
+            println!(
+                "{}",
+                format!("📍 energy {:.2} · positivity {:.2} → {}", energy, positivity, mood.name).dimmed()
+            );
+        }
+        return Some(mood.name.clone());
+    }
+    cli.mood.as_deref().map(|input| resolve_mood(registry, input, format, cli.explain))
+}
+
+fn parse_unit(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(v) if (0.0..=1.0).contains(&v) => Ok(v),
+        Ok(_) => Err("must be between 0 and 1".to_string()),
+        Err(_) => Err(format!("'{}' is not a number", value)),
+    }
+}
+
+fn is_same_name(input: &str, name: &str) -> bool {
+    input.trim().eq_ignore_ascii_case(name)
+}
+
+/// A random mood, weighted by the time-of-day and weekday schedule and by
+/// how the moods have been rated. A `seeded` run ignores the schedule, so
+/// the same seed picks the same mood at any hour.
+fn random_mood(registry: &MoodRegistry, prefs: &Prefs, rng: &mut impl Rng, seeded: bool, why: bool) -> String {
+    let schedule = if seeded {
+        schedule::Schedule::none()
+    } else {
+        schedule::Schedule::load(registry).unwrap_or_else(|err| {
+            eprintln!("{} {}", "⚠️  Ignoring custom schedule:".yellow(), err);
+            schedule::Schedule::builtin()
+        })
+    };
+    let now = Local::now().naive_local();
+    let mut weights = schedule.weigh(registry, now);
+    prefs.adjust(&mut weights);
+    let mood = schedule::choose(&weights, rng).to_string();
+    if why {
+        schedule::print_why(&weights, &mood, now);
+    }
+    mood
+}
+
+/// FNV-1a of the date and team name, which unlike `std`'s randomly keyed
+/// hasher gives every machine the same seed.
+fn daily_seed(date: NaiveDate, team: Option<&str>) -> u64 {
+    let team = team.map(str::to_lowercase).unwrap_or_default();
+    format!("{}|{}", date, team)
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn run_today(team: Option<&str>, date: NaiveDate, ascii: bool, format: Option<OutputFormat>) {
+    let team = team.map(str::trim).filter(|team| !team.is_empty());
+    // Built-in moods only, so someone's moods.toml can't split the team
+    let mut registry = MoodRegistry::builtin();
+    let mut rng = StdRng::seed_from_u64(daily_seed(date, team));
+    registry.roll(&mut rng, &HashMap::new(), |_, _, _| 1.0);
+    say_mottos(&mut registry, None, None);
+    // Uniform, so the time of day someone runs it can't change the answer
+    let mood = get_available_moods(&registry)
+        .choose(&mut rng)
+        .expect("the registry always has the built-in moods")
+        .to_string();
+
+    if let Some(format) = format {
+        print_formatted(&registry, &mood, None, ascii, false, format, Some(Day { date, team }));
+        return;
+    }
+
+    let who = match team {
+        Some(team) => format!(" for team {}", team),
+        None => String::new(),
+    };
+    println!("{}", format!("☀️  Vibe of the day{}, {}", who, date.format("%A %Y-%m-%d")).yellow());
+    let response = get_vibe_response(&registry, &mood).expect("picked from the registry");
+    println!("{}", palette::bold(&mood.to_uppercase(), response.palette.primary));
+    if ascii {
+        print_art(&registry, &mood);
+    }
+    print_vibe_response(&response, None);
+    record_history(&registry, &mood, Event::Pick { at: Local::now() });
+}
+
+fn print_formatted(
+    registry: &MoodRegistry,
+    mood: &str,
+    requested: Option<&str>,
+    ascii: bool,
+    explain: bool,
+    format: OutputFormat,
+    day: Option<Day>,
+) {
+    let found = registry.get(mood).expect("mood was resolved against the registry");
+    let response = get_vibe_response(registry, mood).expect("mood was resolved against the registry");
+    let report = VibeReport {
+        mood: &found.name,
+        requested: requested.map(str::trim).filter(|input| !is_same_name(input, &found.name)),
+        response: &response,
+        ascii_art: if ascii {
+            get_ascii_art(registry, mood).map(|art| art.trim_matches('\n'))
+        } else {
+            None
+        },
+        explanation: requested
+            .filter(|input| explain && !matches!(registry.lookup(input), Lookup::Found(_)))
+            .and_then(|input| detect::detect(registry, input)),
+        day,
+    };
+    println!("{}", output::render(&report, format));
+    record_history(registry, &found.name, Event::Pick { at: Local::now() });
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    cli.color.apply();
+    effects::set_animation(cli.animate);
+
+    if let Some(command) = cli.command.take() {
+        match command {
+            Command::History { action, filter, limit } => run_history(action, filter, limit),
+            Command::Stats { weeks } => {
+                let registry = load_registry();
+                let entries = history::load().unwrap_or_else(|err| {
+                    eprintln!("{} {}", "❌ Could not read history:".red(), err);
+                    std::process::exit(1);
+                });
+                let stats = stats::Stats::new(&entries, Local::now().date_naive());
+                stats::print_report(&stats, &registry, weeks);
+            }
+            Command::Today { team, date, ascii, format } => {
+                run_today(team.as_deref(), date.unwrap_or_else(|| Local::now().date_naive()), ascii, format)
+            }
+            Command::Rate { score, id } => run_rate(score, id),
+            Command::Prefs { reset, mood } => run_prefs(reset, mood),
+            Command::Art { action } => run_art(action),
+        }
+        return;
+    }
     
     // Honeypot responses (check these first)
     if cli.secret_mode {
@@ -544,61 +1399,133 @@ fn main() {
         return;
     }
 
+    let banner = cli.banner.as_deref().map(|name| {
+        figlet::load(name).unwrap_or_else(|err| {
+            eprintln!("{} {}", "❌ Could not load font:".red(), err);
+            std::process::exit(1);
+        })
+    });
+    let mut registry = load_registry();
+    let mut rng = match cli.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    // Variants shown lately are skipped and ratings bias the picks, unless
+    // the run has to be reproducible. An unreadable history just means
+    // nothing is
+    let entries = match cli.seed {
+        Some(_) => Vec::new(),
+        None => history::load().unwrap_or_default(),
+    };
+    let recent = history::recent_variants(&entries);
+    let prefs = Prefs::from_history(&entries);
+    registry.roll(&mut rng, &recent, |mood, part, index| prefs.variant_weight(mood, part, index));
+    cli.mood = cli.mood.take().or(cli.mood_option.take());
+
+    // A blend becomes a mood of its own for the rest of the run
+    if let Some(input) = cli.mood.clone().filter(|input| blend::is_blend(input)) {
+        cli.mood = Some(add_blend(&mut registry, &input, cli.format, &mut rng));
+    }
+    if let Some(effect) = cli.effect {
+        registry.set_effect(effect);
+    }
+    let cow = cli.cow.as_deref().map(|name| {
+        cowsay::load(name).unwrap_or_else(|err| {
+            eprintln!("{} {}", "❌ Could not load cow:".red(), err);
+            std::process::exit(1);
+        })
+    });
+    say_mottos(&mut registry, cow.as_ref(), cli.think.then_some(Bubble::Think));
+    let ascii = cli.ascii || cli.cow.is_some();
+
     // --interactive
     if cli.interactive {
-        interactive_mode();
+        interactive_mode(&registry, tui::select_mood, banner.as_ref());
+        return;
+    }
+
+    // --grid
+    if cli.grid {
+        interactive_mode(&registry, tui::select_on_grid, banner.as_ref());
+        return;
+    }
+
+    // --unblock
+    if cli.unblock {
+        end_focus_mode(&cli.hosts_file);
         return;
     }
 
     // --timer
     if let Some(timer_minutes) = cli.timer {
-        let mood = cli.mood.clone().unwrap_or_else(|| {
-            let moods = get_available_moods();
-            moods.choose(&mut rand::thread_rng()).unwrap().to_string()
-        });
-        pomodoro_timer(timer_minutes, &mood);
+        let mood = requested_mood(&registry, &cli, None).unwrap_or_else(|| random_mood(&registry, &prefs, &mut rng, cli.seed.is_some(), cli.why));
+        if cli.focus_mode {
+            start_focus_mode(&registry, Some(&mood), &cli.hosts_file);
+            // Don't leave sites blocked if the session is cut short
+            let hosts = cli.hosts_file.clone();
+            ctrlc::set_handler(move || {
+                println!();
+                end_focus_mode(&hosts);
+                std::process::exit(130);
+            })
+            .expect("Failed to install Ctrl+C handler");
+        }
+        let plan = Plan {
+            work_minutes: timer_minutes,
+            short_break_minutes: cli.short_break,
+            long_break_minutes: cli.long_break,
+            long_break_every: cli.long_break_every,
+            cycles: cli.cycles,
+        };
+        let finished = pomodoro_timer(&registry, &plan, &mood, banner.as_ref());
+        // Unblock before asking, so an unanswered prompt can't keep the
+        // sites blocked
+        if cli.focus_mode {
+            end_focus_mode(&cli.hosts_file);
+        }
+        if let Some((name, id)) = finished {
+            ask_rating(&registry, &name, id);
+        }
         return;
     }
 
     // --focus-mode
     if cli.focus_mode {
-        println!("[FOCUS MODE] Blocking distracting websites (feature coming soon)");
-        // TODO: Implement focus mode
+        let mood = requested_mood(&registry, &cli, None);
+        start_focus_mode(&registry, mood.as_deref(), &cli.hosts_file);
+        println!("{}", "   Run `vibe --unblock` to restore".dimmed());
         return;
     }
 
     // --workspace
     if cli.workspace {
-        println!("[WORKSPACE] Setting up VS Code for the vibe (feature coming soon)");
-        // TODO: Implement workspace setup
+        let mood = requested_mood(&registry, &cli, None).unwrap_or_else(|| random_mood(&registry, &prefs, &mut rng, cli.seed.is_some(), cli.why));
+        setup_workspace(&registry, &mood, cli.dry_run);
         return;
     }
     
-    let mood = match cli.mood {
-        Some(m) => m,
+    let mood = match requested_mood(&registry, &cli, cli.format) {
+        Some(mood) => mood,
         None => {
-            let available_moods = get_available_moods();
-            let random_mood = available_moods.choose(&mut rand::thread_rng())
-                .expect("Failed to select random mood");
-            println!("{}", "🎲 No mood specified, choosing randomly...".yellow());
-            random_mood.to_string()
+            if cli.format.is_none() {
+                println!("{}", "🎲 No mood specified, choosing randomly...".yellow());
+            }
+            random_mood(&registry, &prefs, &mut rng, cli.seed.is_some(), cli.why && cli.format.is_none())
         }
     };
+
+    if let Some(format) = cli.format {
+        print_formatted(&registry, &mood, cli.mood.as_deref(), ascii, cli.explain, format, None);
+        return;
+    }
     
-    if cli.ascii {
-        if let Some(ascii) = get_ascii_art(&mood) {
-            println!("{}", ascii.cyan());
-        }
+    if ascii {
+        print_art(&registry, &mood);
     }
     
-    match get_vibe_response(&mood) {
-        Some(response) => {
-            print_vibe_response(&response);
-        }
-        None => {
-            eprintln!("{}", "❌ Unknown mood!".red());
-            eprintln!("Available moods: {}", "focus, chaotic, sadboi".cyan());
-            std::process::exit(1);
-        }
+    let response = get_vibe_response(&registry, &mood).expect("mood was resolved against the registry");
+    print_vibe_response(&response, banner.as_ref());
+    if record_history(&registry, &mood, Event::Pick { at: Local::now() }).is_some() {
+        println!("{}", "   Liked it? `vibe rate 1-5` (or up/down) tunes future random picks".dimmed());
     }
 } 
\ No newline at end of file
@@ -0,0 +1,1273 @@
+use crate::{
+    art::{self, ArtPack, Kind},
+    cowsay::{self, Bubble, Cow},
+    effects::{self, Effect},
+    palette::{hex, Palette, Rgb},
+    paths,
+};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    path::Path,
+};
+
+/// Emoji and message used by the timer when a mood does not define its own.
+pub const DEFAULT_TIMER_EMOJI: &str = "⏳";
+pub const DEFAULT_TIMER_MESSAGE: &str = "Time to vibe!";
+pub const DEFAULT_BREAK_ART: &str = "\n  (•_•)  ( •_•)>⌐■-■  (⌐■_■)\nBreak like a boss!";
+
+/// One-line description for custom moods that don't write their own.
+pub const DEFAULT_DESCRIPTION: &str = "Custom mood";
+
+/// Energy and positivity of custom moods that don't place themselves.
+pub const DEFAULT_COORDINATE: f64 = 0.5;
+
+/// Color the palette of custom moods is built around when they pick neither
+/// a `color` nor a `palette`.
+pub const DEFAULT_COLOR: &str = "white";
+
+/// VS Code color theme for moods that don't pick one.
+pub const DEFAULT_VSCODE_THEME: &str = "Default Dark Modern";
+
+/// Sites blocked by `--focus-mode` for moods without their own list.
+pub const DEFAULT_BLOCKED_SITES: &[&str] = &[
+    "youtube.com",
+    "reddit.com",
+    "twitter.com",
+    "x.com",
+    "facebook.com",
+    "instagram.com",
+    "tiktok.com",
+];
+
+/// Everything the app knows about a single mood.
+#[derive(Debug, Clone)]
+pub struct Mood {
+    pub name: String,
+    pub description: String,
+    pub theme: String,
+    /// The colors `theme` stands for, used to draw the report
+    pub palette: Palette,
+    /// How art, the motto and headers are colored
+    pub effect: Effect,
+    /// Colors `effect` runs through. Empty means primary to accent
+    pub gradient: Vec<Rgb>,
+    /// How activated the mood is, from 0 (drowsy) to 1 (wired)
+    pub energy: f64,
+    /// How pleasant the mood is, from 0 (miserable) to 1 (elated)
+    pub positivity: f64,
+    /// This run's picks from `variants`, see [`MoodRegistry::roll`]
+    pub music: String,
+    pub stack: String,
+    pub motto: String,
+    pub ascii_art: Option<String>,
+    /// A `.cow` character that says the motto in place of the art
+    pub cow: Option<String>,
+    pub bubble: Bubble,
+    pub variants: Variants,
+    /// Which of `variants` are picked. `None` for moods made up on the fly,
+    /// like blends, which have nothing to pick from.
+    pub variant: Option<Variant>,
+    pub timer_emoji: String,
+    pub timer_message: String,
+    pub break_art: String,
+    pub blocked_sites: Vec<String>,
+    pub vscode_theme: String,
+    /// Marketplace id of the extension providing `vscode_theme`, if it isn't
+    /// one of the themes bundled with VS Code.
+    pub vscode_theme_extension: Option<String>,
+    /// Whether `--workspace` should turn on distraction-free editor settings.
+    pub vscode_zen: bool,
+}
+
+/// Every option a mood's music, stack, motto and art are picked from. All but
+/// `art` have at least one entry.
+#[derive(Debug, Clone, Default)]
+pub struct Variants {
+    pub music: Vec<String>,
+    pub stacks: Vec<String>,
+    pub mottos: Vec<String>,
+    pub art: Vec<String>,
+    /// Break art, one picked per run. Not kept in the history
+    pub breaks: Vec<String>,
+}
+
+/// Indexes into [`Variants`], kept in the history so the next run can avoid
+/// repeating them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Variant {
+    pub music: usize,
+    pub stack: usize,
+    pub motto: usize,
+    pub art: usize,
+}
+
+/// One of the four things a [`Variant`] picks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    Music,
+    Stack,
+    Motto,
+    Art,
+}
+
+impl Part {
+    pub const ALL: [Part; 4] = [Part::Music, Part::Stack, Part::Motto, Part::Art];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Part::Music => "music",
+            Part::Stack => "stack",
+            Part::Motto => "motto",
+            Part::Art => "art",
+        }
+    }
+}
+
+impl Variant {
+    pub fn get(&self, part: Part) -> usize {
+        match part {
+            Part::Music => self.music,
+            Part::Stack => self.stack,
+            Part::Motto => self.motto,
+            Part::Art => self.art,
+        }
+    }
+}
+
+/// All known moods: the built-ins, overridden and extended by the user's
+/// `moods.toml`.
+#[derive(Debug)]
+pub struct MoodRegistry {
+    moods: Vec<Mood>,
+    /// Normalized alias -> canonical mood name
+    aliases: BTreeMap<String, String>,
+    /// Moods whose `cow` was picked in `moods.toml`, which art files don't
+    /// take the place of
+    chosen_cows: BTreeSet<String>,
+}
+
+/// One `[moods.<name>]` table in `moods.toml`. Every field is optional so a
+/// user can tweak a single value of a built-in mood.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MoodConfig {
+    description: Option<String>,
+    theme: Option<String>,
+    /// Shorthand for a palette built around one color
+    color: Option<String>,
+    palette: Option<PaletteConfig>,
+    effect: Option<Effect>,
+    gradient: Option<Vec<String>>,
+    energy: Option<f64>,
+    positivity: Option<f64>,
+    music: Option<OneOrMany>,
+    stack: Option<OneOrMany>,
+    motto: Option<OneOrMany>,
+    ascii_art: Option<OneOrMany>,
+    cow: Option<String>,
+    bubble: Option<Bubble>,
+    timer_emoji: Option<String>,
+    timer_message: Option<String>,
+    break_art: Option<OneOrMany>,
+    blocked_sites: Option<Vec<String>>,
+    vscode_theme: Option<String>,
+    vscode_theme_extension: Option<String>,
+    vscode_zen: Option<bool>,
+}
+
+/// `[moods.<name>.palette]`: any of the four colors, each a name or "#rrggbb".
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PaletteConfig {
+    primary: Option<String>,
+    secondary: Option<String>,
+    accent: Option<String>,
+    background: Option<String>,
+}
+
+impl PaletteConfig {
+    fn colors(&self) -> [(&'static str, &Option<String>); 4] {
+        [
+            ("palette.primary", &self.primary),
+            ("palette.secondary", &self.secondary),
+            ("palette.accent", &self.accent),
+            ("palette.background", &self.background),
+        ]
+    }
+
+    /// `base` with the colors this table sets. Colors are checked on load.
+    fn apply_to(&self, base: Palette) -> Palette {
+        let color = |value: &Option<String>, fallback: Rgb| value.as_deref().and_then(Rgb::parse).unwrap_or(fallback);
+        Palette {
+            primary: color(&self.primary, base.primary),
+            secondary: color(&self.secondary, base.secondary),
+            accent: color(&self.accent, base.accent),
+            background: color(&self.background, base.background),
+        }
+    }
+}
+
+/// `motto = "..."`, or `motto = ["...", "..."]` for one picked per run.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    moods: BTreeMap<String, MoodConfig>,
+    /// `alias = "mood"` pairs, added to (or replacing) the built-in aliases
+    aliases: BTreeMap<String, String>,
+}
+
+impl MoodRegistry {
+    /// Only the moods that ship with vibe.
+    pub fn builtin() -> Self {
+        MoodRegistry {
+            moods: BUILTIN_MOODS.iter().map(BuiltinMood::to_mood).collect(),
+            aliases: BUILTIN_ALIASES
+                .iter()
+                .flat_map(|(mood, aliases)| aliases.iter().map(move |alias| (normalize(alias), mood.to_string())))
+                .collect(),
+            chosen_cows: BTreeSet::new(),
+        }
+    }
+
+    /// Built-ins plus `$XDG_CONFIG_HOME/vibe/moods.toml`, if it exists.
+    pub fn load() -> Result<Self, String> {
+        let mut registry = Self::builtin();
+        if let Some(path) = paths::config_dir().map(|dir| dir.join("moods.toml")) {
+            if path.exists() {
+                registry.merge_file(&path)?;
+            }
+        }
+        Ok(registry)
+    }
+
+    fn merge_file(&mut self, path: &Path) -> Result<(), String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let config: ConfigFile = toml::from_str(&contents)
+            .map_err(|e| format!("failed to parse {}: {}", path.display(), e))?;
+
+        for (name, config) in config.moods {
+            let name = name.to_lowercase();
+            // "a+b" and "a:2" would be read as a blend
+            if name.contains(['+', ':']) {
+                return Err(format!(
+                    "{}: mood '{}' can't have '+' or ':' in its name, they're for blends",
+                    path.display(),
+                    name
+                ));
+            }
+            let palette_colors = config.palette.iter().flat_map(PaletteConfig::colors);
+            for (key, color) in [("color", &config.color)].into_iter().chain(palette_colors) {
+                if let Some(color) = color.as_deref().filter(|color| Rgb::parse(color).is_none()) {
+                    return Err(format!(
+                        "{}: mood '{}' has unknown {} '{}'",
+                        path.display(),
+                        name,
+                        key,
+                        color
+                    ));
+                }
+            }
+            for color in config.gradient.iter().flatten() {
+                if Rgb::parse(color).is_none() {
+                    return Err(format!("{}: mood '{}' has unknown gradient color '{}'", path.display(), name, color));
+                }
+            }
+            if config.gradient.as_ref().is_some_and(Vec::is_empty) {
+                return Err(format!("{}: mood '{}' has an empty gradient list", path.display(), name));
+            }
+            for (key, value) in [
+                ("music", &config.music),
+                ("stack", &config.stack),
+                ("motto", &config.motto),
+                ("ascii_art", &config.ascii_art),
+                ("break_art", &config.break_art),
+            ] {
+                if matches!(value, Some(OneOrMany::Many(values)) if values.is_empty()) {
+                    return Err(format!("{}: mood '{}' has an empty {} list", path.display(), name, key));
+                }
+            }
+            for (key, value) in [("energy", config.energy), ("positivity", config.positivity)] {
+                if value.is_some_and(|v| !(0.0..=1.0).contains(&v)) {
+                    return Err(format!(
+                        "{}: mood '{}' has {} outside 0..1",
+                        path.display(),
+                        name,
+                        key
+                    ));
+                }
+            }
+            if config.cow.is_some() {
+                self.chosen_cows.insert(name.clone());
+            }
+            match self.moods.iter_mut().find(|m| m.name == name) {
+                Some(existing) => config.apply_to(existing),
+                None => {
+                    // Names are matched normalized, so "release-day" and
+                    // "release_day" would be the same mood
+                    if let Some(other) = self.moods.iter().find(|m| normalize(&m.name) == normalize(&name)) {
+                        return Err(format!(
+                            "{}: mood '{}' can't be told apart from '{}'",
+                            path.display(),
+                            name,
+                            other.name
+                        ));
+                    }
+                    let mood = config
+                        .into_mood(&name)
+                        .map_err(|e| format!("{}: {}", path.display(), e))?;
+                    self.moods.push(mood);
+                }
+            }
+        }
+
+        // Checked after the moods so an alias can point at one defined above
+        for (alias, target) in config.aliases {
+            let target = target.to_lowercase();
+            if !self.moods.iter().any(|m| m.name == target) {
+                return Err(format!(
+                    "{}: alias '{}' points at unknown mood '{}'",
+                    path.display(),
+                    alias,
+                    target
+                ));
+            }
+            self.aliases.insert(normalize(&alias), target);
+        }
+        Ok(())
+    }
+
+    /// A mood by its name or one of its aliases.
+    pub fn get(&self, name: &str) -> Option<&Mood> {
+        let key = normalize(name);
+        self.moods
+            .iter()
+            .find(|m| normalize(&m.name) == key)
+            .or_else(|| self.moods.iter().find(|m| Some(&m.name) == self.aliases.get(&key)))
+    }
+
+    /// Resolve what the user typed: an exact name or alias, or a prefix that
+    /// only one mood starts with. Otherwise suggest close matches.
+    pub fn lookup(&self, input: &str) -> Lookup<'_> {
+        let input = normalize(input);
+        if let Some(mood) = self.get(&input) {
+            return Lookup::Found(mood);
+        }
+
+        let prefixed: Vec<&Mood> = self.moods.iter().filter(|m| normalize(&m.name).starts_with(&input)).collect();
+        match prefixed.as_slice() {
+            [mood] if !input.is_empty() => return Lookup::Found(mood),
+            [_, _, ..] if !input.is_empty() => {
+                return Lookup::Ambiguous(prefixed.iter().map(|m| m.name.as_str()).collect())
+            }
+            _ => {}
+        }
+
+        // Allow roughly one typo per three letters, and at least two
+        let max_distance = (input.chars().count() / 3).max(2);
+        // Aliases count as spellings of their mood, so "sadd" still finds sadboi
+        let spellings = self
+            .moods
+            .iter()
+            .map(|m| (normalize(&m.name), m.name.as_str()))
+            .chain(self.aliases.iter().map(|(alias, target)| (alias.clone(), target.as_str())));
+        let mut close: Vec<(usize, &str)> = spellings
+            .map(|(spelling, name)| (edit_distance(&input, &spelling), name))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .collect();
+        close.sort();
+        let mut names: Vec<&str> = Vec::new();
+        for (_, name) in close {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names.truncate(3);
+        Lookup::Unknown(names)
+    }
+
+    /// Pick this run's music, stack, motto and art for every mood at random,
+    /// steering clear of the variants `recent` (newest first) says were
+    /// shown lately. `preference` scales each remaining variant's chance.
+    pub fn roll(
+        &mut self,
+        rng: &mut impl Rng,
+        recent: &HashMap<String, Vec<Variant>>,
+        preference: impl Fn(&str, Part, usize) -> f64,
+    ) {
+        for mood in &mut self.moods {
+            let seen = recent.get(&mood.name).map_or(&[][..], Vec::as_slice);
+            let mut pick_part = |part: Part, count: usize| {
+                pick(rng, count, seen.iter().map(|v| v.get(part)), |i| preference(&mood.name, part, i))
+            };
+            let variant = Variant {
+                music: pick_part(Part::Music, mood.variants.music.len()),
+                stack: pick_part(Part::Stack, mood.variants.stacks.len()),
+                motto: pick_part(Part::Motto, mood.variants.mottos.len()),
+                art: pick_part(Part::Art, mood.variants.art.len()),
+            };
+            mood.select(variant);
+            if mood.variants.breaks.len() > 1 {
+                mood.break_art = mood.variants.breaks.choose(rng).cloned().unwrap_or_default();
+            }
+        }
+    }
+
+    /// Swap in the art from `pack`: a mood with art files shows those instead
+    /// of its built-in or `moods.toml` art, and likewise for break art. Art
+    /// files also replace a built-in character, but not one picked in
+    /// `moods.toml`. Files for moods that don't exist are left out.
+    pub fn add_art(&mut self, pack: &ArtPack) {
+        for mood in &mut self.moods {
+            let art: Vec<String> = pack.for_mood(&mood.name, Kind::Art).map(|file| art::art_text(&file.text)).collect();
+            let breaks: Vec<String> = pack.for_mood(&mood.name, Kind::Break).map(|file| file.text.clone()).collect();
+            if art.is_empty() && breaks.is_empty() {
+                continue;
+            }
+            if !art.is_empty() {
+                mood.variants.art = art;
+                if !self.chosen_cows.contains(&mood.name) {
+                    mood.cow = None;
+                }
+            }
+            if !breaks.is_empty() {
+                mood.variants.breaks = breaks;
+            }
+            mood.select(mood.variant.unwrap_or_default());
+        }
+    }
+
+    /// Color every mood with `effect`, for `--effect`.
+    pub fn set_effect(&mut self, effect: Effect) {
+        for mood in &mut self.moods {
+            mood.effect = effect;
+        }
+    }
+
+    /// Have each mood's character say its motto, in place of the art. `cow`
+    /// (from `--cow`) stands in for every mood's own, giving one to moods
+    /// without, and `bubble` for each mood's bubble. Returns an error for
+    /// each mood whose character couldn't be loaded.
+    pub fn say_mottos(&mut self, cow: Option<&Cow>, bubble: Option<Bubble>, width: usize) -> Vec<String> {
+        let mut errors = Vec::new();
+        for mood in &mut self.moods {
+            let own;
+            let cow = match (cow, &mood.cow) {
+                (Some(cow), _) => cow,
+                (None, Some(name)) => match cowsay::load(name) {
+                    Ok(loaded) => {
+                        own = loaded;
+                        &own
+                    }
+                    Err(err) => {
+                        errors.push(format!("mood '{}': {}", mood.name, err));
+                        continue;
+                    }
+                },
+                (None, None) => continue,
+            };
+            mood.ascii_art = Some(cow.say(&mood.motto, bubble.unwrap_or(mood.bubble), width));
+        }
+        errors
+    }
+
+    /// Add a mood that only lives for this run, such as a blend, replacing
+    /// any mood of the same name.
+    pub fn add(&mut self, mood: Mood) {
+        self.moods.retain(|m| m.name != mood.name);
+        self.moods.push(mood);
+    }
+
+    /// The mood closest to a point on the energy/positivity plane.
+    pub fn nearest(&self, energy: f64, positivity: f64) -> &Mood {
+        let distance = |m: &Mood| (m.energy - energy).powi(2) + (m.positivity - positivity).powi(2);
+        self.moods
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+            .expect("the registry always has the built-in moods")
+    }
+
+    pub fn all(&self) -> &[Mood] {
+        &self.moods
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.moods.iter().map(|m| m.name.as_str()).collect()
+    }
+}
+
+/// Result of [`MoodRegistry::lookup`].
+pub enum Lookup<'a> {
+    Found(&'a Mood),
+    /// A prefix shared by several moods
+    Ambiguous(Vec<&'a str>),
+    /// Nothing matched; the closest names, best first
+    Unknown(Vec<&'a str>),
+}
+
+/// A random index below `count`, avoiding the most recent half of `recent`
+/// so a mood with a few variants doesn't show the same one twice running.
+/// The rest are drawn in proportion to `weight`.
+fn pick(
+    rng: &mut impl Rng,
+    count: usize,
+    recent: impl Iterator<Item = usize>,
+    weight: impl Fn(usize) -> f64,
+) -> usize {
+    if count <= 1 {
+        return 0;
+    }
+    let avoid: Vec<usize> = recent.take(count / 2).collect();
+    let fresh: Vec<usize> = (0..count).filter(|i| !avoid.contains(i)).collect();
+    match fresh.choose_weighted(rng, |i| weight(*i)) {
+        Ok(index) => *index,
+        Err(_) => *fresh.choose(rng).expect("at most half the variants are avoided"),
+    }
+}
+
+/// Gradient stops from `moods.toml`, whose colors are checked on load.
+fn parse_gradient(colors: &[String]) -> Vec<Rgb> {
+    colors.iter().filter_map(|color| Rgb::parse(color)).collect()
+}
+
+/// Case, surrounding space, separators and emoji variation selectors don't
+/// matter when matching names and aliases: "Deep Work", "deep-work" and
+/// "deepwork" are the same, and so are "☯️" and "☯".
+fn normalize(input: &str) -> String {
+    input
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_' | '\u{fe0f}'))
+        .collect()
+}
+
+/// Levenshtein distance between two strings, by characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+impl Mood {
+    /// The colors `effect` runs through.
+    pub fn gradient_stops(&self) -> Vec<Rgb> {
+        if self.gradient.is_empty() {
+            vec![self.palette.primary, self.palette.accent]
+        } else {
+            self.gradient.clone()
+        }
+    }
+
+    /// `text` colored with this mood's effect.
+    pub fn paint(&self, text: &str) -> String {
+        effects::render(text, self.effect, &self.gradient_stops(), 0.0)
+    }
+
+    pub fn select(&mut self, variant: Variant) {
+        let nth = |list: &[String], i: usize| list.get(i).or(list.first()).cloned();
+        self.music = nth(&self.variants.music, variant.music).unwrap_or_default();
+        self.stack = nth(&self.variants.stacks, variant.stack).unwrap_or_default();
+        self.motto = nth(&self.variants.mottos, variant.motto).unwrap_or_default();
+        self.ascii_art = nth(&self.variants.art, variant.art);
+        self.break_art = nth(&self.variants.breaks, 0).unwrap_or_else(|| DEFAULT_BREAK_ART.to_string());
+        self.variant = Some(variant);
+    }
+}
+
+impl MoodConfig {
+    fn apply_to(self, mood: &mut Mood) {
+        if let Some(description) = self.description {
+            mood.description = description;
+        }
+        if let Some(theme) = self.theme {
+            mood.theme = theme;
+        }
+        // A new color replaces the whole palette, the table then tweaks it
+        if let Some(color) = self.color.as_deref().and_then(Rgb::parse) {
+            mood.palette = Palette::from_color(color);
+        }
+        if let Some(palette) = &self.palette {
+            mood.palette = palette.apply_to(mood.palette);
+        }
+        if let Some(effect) = self.effect {
+            mood.effect = effect;
+        }
+        if let Some(gradient) = &self.gradient {
+            mood.gradient = parse_gradient(gradient);
+        } else if self.color.is_some() || self.palette.is_some() {
+            // Built-in stops would clash with the new colors
+            mood.gradient.clear();
+        }
+        if let Some(energy) = self.energy {
+            mood.energy = energy;
+        }
+        if let Some(positivity) = self.positivity {
+            mood.positivity = positivity;
+        }
+        if let Some(music) = self.music {
+            mood.variants.music = music.into_vec();
+        }
+        if let Some(stack) = self.stack {
+            mood.variants.stacks = stack.into_vec();
+        }
+        if let Some(motto) = self.motto {
+            mood.variants.mottos = motto.into_vec();
+        }
+        if let Some(ascii_art) = self.ascii_art {
+            mood.variants.art = ascii_art.into_vec();
+            if self.cow.is_none() {
+                // The user's own art over the built-in character
+                mood.cow = None;
+            }
+        }
+        if let Some(break_art) = self.break_art {
+            mood.variants.breaks = break_art.into_vec();
+        }
+        if let Some(cow) = self.cow {
+            mood.cow = Some(cow);
+        }
+        if let Some(bubble) = self.bubble {
+            mood.bubble = bubble;
+        }
+        mood.select(Variant::default());
+        if let Some(timer_emoji) = self.timer_emoji {
+            mood.timer_emoji = timer_emoji;
+        }
+        if let Some(timer_message) = self.timer_message {
+            mood.timer_message = timer_message;
+        }
+        if let Some(blocked_sites) = self.blocked_sites {
+            mood.blocked_sites = blocked_sites;
+        }
+        if let Some(vscode_theme) = self.vscode_theme {
+            mood.vscode_theme = vscode_theme;
+            // A new theme name makes the built-in extension meaningless
+            mood.vscode_theme_extension = self.vscode_theme_extension;
+        } else if let Some(extension) = self.vscode_theme_extension {
+            mood.vscode_theme_extension = Some(extension);
+        }
+        if let Some(vscode_zen) = self.vscode_zen {
+            mood.vscode_zen = vscode_zen;
+        }
+    }
+
+    fn custom_palette(&self) -> Palette {
+        let color = self.color.as_deref().unwrap_or(DEFAULT_COLOR);
+        let base = Palette::from_color(Rgb::parse(color).expect("colors are checked on load"));
+        self.palette.as_ref().map_or(base, |palette| palette.apply_to(base))
+    }
+
+    /// Build a brand new mood. The four report fields are required, the rest
+    /// fall back to the generic timer defaults.
+    fn into_mood(self, name: &str) -> Result<Mood, String> {
+        let missing = |key: &str| format!("mood '{}' is missing required key '{}'", name, key);
+        let require = |field: Option<String>, key: &str| field.ok_or_else(|| missing(key));
+        let require_list = |field: Option<OneOrMany>, key: &str| field.map(OneOrMany::into_vec).ok_or_else(|| missing(key));
+        let palette = self.custom_palette();
+        let mut mood = Mood {
+            name: name.to_string(),
+            description: self.description.unwrap_or_else(|| DEFAULT_DESCRIPTION.to_string()),
+            theme: require(self.theme, "theme")?,
+            palette,
+            effect: self.effect.unwrap_or_default(),
+            gradient: self.gradient.as_deref().map(parse_gradient).unwrap_or_default(),
+            energy: self.energy.unwrap_or(DEFAULT_COORDINATE),
+            positivity: self.positivity.unwrap_or(DEFAULT_COORDINATE),
+            music: String::new(),
+            stack: String::new(),
+            motto: String::new(),
+            ascii_art: None,
+            cow: self.cow,
+            bubble: self.bubble.unwrap_or_default(),
+            variants: Variants {
+                music: require_list(self.music, "music")?,
+                stacks: require_list(self.stack, "stack")?,
+                mottos: require_list(self.motto, "motto")?,
+                art: self.ascii_art.map(OneOrMany::into_vec).unwrap_or_default(),
+                breaks: self.break_art.map_or_else(|| vec![DEFAULT_BREAK_ART.to_string()], OneOrMany::into_vec),
+            },
+            variant: None,
+            timer_emoji: self.timer_emoji.unwrap_or_else(|| DEFAULT_TIMER_EMOJI.to_string()),
+            timer_message: self.timer_message.unwrap_or_else(|| DEFAULT_TIMER_MESSAGE.to_string()),
+            break_art: String::new(),
+            blocked_sites: self.blocked_sites.unwrap_or_else(|| {
+                DEFAULT_BLOCKED_SITES.iter().map(|s| s.to_string()).collect()
+            }),
+            vscode_theme: self.vscode_theme.unwrap_or_else(|| DEFAULT_VSCODE_THEME.to_string()),
+            vscode_theme_extension: self.vscode_theme_extension,
+            vscode_zen: self.vscode_zen.unwrap_or(false),
+        };
+        mood.select(Variant::default());
+        Ok(mood)
+    }
+}
+
+struct BuiltinMood {
+    name: &'static str,
+    description: &'static str,
+    theme: &'static str,
+    palette: Palette,
+    effect: Effect,
+    gradient: &'static [Rgb],
+    energy: f64,
+    positivity: f64,
+    music: &'static [&'static str],
+    stacks: &'static [&'static str],
+    mottos: &'static [&'static str],
+    /// `art/<name>.txt`, for moods that have art
+    ascii_art: &'static [&'static str],
+    cow: Option<&'static str>,
+    bubble: Bubble,
+    timer_emoji: &'static str,
+    timer_message: &'static str,
+    /// `art/<name>.break.txt`
+    break_art: &'static str,
+    blocked_sites: &'static [&'static str],
+    vscode_theme: &'static str,
+    vscode_theme_extension: Option<&'static str>,
+    vscode_zen: bool,
+}
+
+impl BuiltinMood {
+    fn to_mood(&self) -> Mood {
+        let strings = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        let mut mood = Mood {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            theme: self.theme.to_string(),
+            palette: self.palette,
+            effect: self.effect,
+            gradient: self.gradient.to_vec(),
+            energy: self.energy,
+            positivity: self.positivity,
+            music: String::new(),
+            stack: String::new(),
+            motto: String::new(),
+            ascii_art: None,
+            cow: self.cow.map(str::to_string),
+            bubble: self.bubble,
+            variants: Variants {
+                music: strings(self.music),
+                stacks: strings(self.stacks),
+                mottos: strings(self.mottos),
+                art: self.ascii_art.iter().map(|text| art::art_text(text)).collect(),
+                breaks: vec![art::break_text(self.break_art)],
+            },
+            variant: None,
+            timer_emoji: self.timer_emoji.to_string(),
+            timer_message: self.timer_message.to_string(),
+            break_art: String::new(),
+            blocked_sites: strings(self.blocked_sites),
+            vscode_theme: self.vscode_theme.to_string(),
+            vscode_theme_extension: self.vscode_theme_extension.map(str::to_string),
+            vscode_zen: self.vscode_zen,
+        };
+        mood.select(Variant::default());
+        mood
+    }
+}
+
+const BUILTIN_MOODS: &[BuiltinMood] = &[
+    BuiltinMood {
+        name: "focus",
+        description: "For deep work and concentration",
+        theme: "Deep Ocean Blue",
+        palette: Palette {
+            primary: hex(0x3a8fd9),
+            secondary: hex(0x1b4f80),
+            accent: hex(0x7fd4ff),
+            background: hex(0x06182b),
+        },
+        effect: Effect::Vertical,
+        gradient: &[hex(0x7fd4ff), hex(0x3a8fd9), hex(0x1b4f80)],
+        energy: 0.5,
+        positivity: 0.6,
+        music: &[
+            "Lo-fi beats, classical piano, ambient nature sounds",
+            "Deep house instrumentals, brown noise, minimalist strings",
+            "Video game soundtracks, post-rock, rain sounds",
+        ],
+        stacks: &[
+            "Rust, TypeScript, PostgreSQL, Docker",
+            "Go, SQLite, Make, a single terminal",
+            "Python, pytest, Jupyter, Vim",
+        ],
+        mottos: &[
+            "Flow state is the goal state",
+            "One thing at a time, done properly",
+            "Notifications can wait, the work can't",
+        ],
+        ascii_art: &[],
+        cow: Some("tux"),
+        bubble: Bubble::Say,
+        timer_emoji: "🔵",
+        timer_message: "Stay focused!",
+        break_art: include_str!("../art/focus.break.txt"),
+        blocked_sites: &["youtube.com", "reddit.com", "twitter.com", "x.com", "facebook.com", "instagram.com", "tiktok.com", "news.ycombinator.com", "twitch.tv"],
+        vscode_theme: "Abyss",
+        vscode_theme_extension: None,
+        vscode_zen: true,
+    },
+    BuiltinMood {
+        name: "chaotic",
+        description: "For high-energy, experimental coding",
+        theme: "Neon Cyberpunk",
+        palette: Palette {
+            primary: hex(0xff2bd6),
+            secondary: hex(0x7b2cff),
+            accent: hex(0x00f0ff),
+            background: hex(0x12002a),
+        },
+        effect: Effect::Horizontal,
+        gradient: &[hex(0xff2bd6), hex(0x7b2cff), hex(0x00f0ff)],
+        energy: 0.95,
+        positivity: 0.5,
+        music: &[
+            "EDM, industrial rock, experimental electronic",
+            "Breakcore, drum and bass, glitch hop",
+            "Hyperpop, nu metal, whatever the algorithm serves",
+        ],
+        stacks: &[
+            "Python, JavaScript, MongoDB, Redis, WebSockets",
+            "Bash one-liners, Perl, cron jobs, production hotfixes",
+            "Elixir, Phoenix LiveView, Redis, chaos monkeys",
+        ],
+        mottos: &[
+            "Embrace the chaos, create order",
+            "Move fast and fix things",
+            "If it compiles on the first try, be suspicious",
+        ],
+        ascii_art: &[],
+        cow: Some("default"),
+        bubble: Bubble::Say,
+        timer_emoji: "⚡",
+        timer_message: "Embrace the chaos!",
+        break_art: include_str!("../art/chaotic.break.txt"),
+        blocked_sites: DEFAULT_BLOCKED_SITES,
+        vscode_theme: "SynthWave '84",
+        vscode_theme_extension: Some("RobbOwen.synthwave-vscode"),
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "sadboi",
+        description: "For introspective, melancholic vibes",
+        theme: "Melancholic Purple",
+        palette: Palette {
+            primary: hex(0x9b7fd1),
+            secondary: hex(0x5a4a8a),
+            accent: hex(0xc9b8f0),
+            background: hex(0x1a1428),
+        },
+        effect: Effect::Vertical,
+        gradient: &[hex(0xc9b8f0), hex(0x9b7fd1), hex(0x5a4a8a)],
+        energy: 0.2,
+        positivity: 0.15,
+        music: &[
+            "Indie folk, post-rock, sad piano ballads",
+            "Slowcore, bedroom pop, lo-fi rain",
+            "Emo revival, ambient drone, late-night jazz",
+        ],
+        stacks: &[
+            "Go, React, SQLite, simple APIs",
+            "Python scripts, Markdown notes, plain SQL",
+            "Lua, LÖVE, tiny games nobody will play",
+        ],
+        mottos: &[
+            "Code through the feels",
+            "It's okay to ship something small today",
+            "Sad code is still code",
+        ],
+        ascii_art: &[include_str!("../art/sadboi.txt")],
+        cow: None,
+        bubble: Bubble::Think,
+        timer_emoji: "💜",
+        timer_message: "Code through the feels!",
+        break_art: include_str!("../art/sadboi.break.txt"),
+        blocked_sites: &["twitter.com", "x.com", "instagram.com", "facebook.com", "tiktok.com", "linkedin.com", "cnn.com", "bbc.com"],
+        vscode_theme: "Shades of Purple",
+        vscode_theme_extension: Some("ahmadawais.shades-of-purple"),
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "energetic",
+        description: "For high-energy, fast-paced development",
+        theme: "Sunset Orange",
+        palette: Palette {
+            primary: hex(0xff7a2f),
+            secondary: hex(0xd43f3a),
+            accent: hex(0xffc94d),
+            background: hex(0x2a0f05),
+        },
+        effect: Effect::Vertical,
+        gradient: &[hex(0xffc94d), hex(0xff7a2f), hex(0xd43f3a)],
+        energy: 0.9,
+        positivity: 0.85,
+        music: &[
+            "Rock, punk, high-energy electronic, workout beats",
+            "Drum and bass, hardstyle, 90s eurodance",
+            "Pop punk, ska, high-BPM synthwave",
+        ],
+        stacks: &[
+            "Node.js, React, Firebase, WebRTC",
+            "Bun, Hono, Tailwind, Vercel",
+            "Go, gRPC, Redis streams, load tests",
+        ],
+        mottos: &[
+            "Code like you're running out of time",
+            "Ship it before the coffee wears off",
+            "Momentum is a feature",
+        ],
+        ascii_art: &[include_str!("../art/energetic.txt")],
+        cow: None,
+        bubble: Bubble::Say,
+        timer_emoji: "🔥",
+        timer_message: "Keep the energy up!",
+        break_art: include_str!("../art/energetic.break.txt"),
+        blocked_sites: &["youtube.com", "reddit.com", "twitch.tv", "tiktok.com", "instagram.com"],
+        vscode_theme: "Monokai",
+        vscode_theme_extension: None,
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "chill",
+        description: "For relaxed, steady coding sessions",
+        theme: "Forest Green",
+        palette: Palette {
+            primary: hex(0x5fb36b),
+            secondary: hex(0x2f6b3a),
+            accent: hex(0xb8e0a0),
+            background: hex(0x0d1f12),
+        },
+        effect: Effect::Flat,
+        gradient: &[],
+        energy: 0.25,
+        positivity: 0.75,
+        music: &[
+            "Jazz, acoustic, nature sounds, smooth R&B",
+            "Bossa nova, city pop, lo-fi hip-hop",
+            "Ambient folk, soft rock, vinyl crackle",
+        ],
+        stacks: &[
+            "Python, Flask, SQLite, simple HTML/CSS",
+            "Ruby, Sinatra, SQLite, plain CSS",
+            "Elm, Parcel, a static site generator",
+        ],
+        mottos: &[
+            "Take it easy, build it right",
+            "Slow is smooth, smooth is fast",
+            "No rush, just progress",
+        ],
+        ascii_art: &[include_str!("../art/chill.txt")],
+        cow: None,
+        bubble: Bubble::Say,
+        timer_emoji: "🌿",
+        timer_message: "Stay chill and code on!",
+        break_art: include_str!("../art/chill.break.txt"),
+        blocked_sites: &["twitter.com", "x.com", "linkedin.com", "news.ycombinator.com", "cnn.com"],
+        vscode_theme: "Everforest Dark",
+        vscode_theme_extension: Some("sainnhe.everforest"),
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "creative",
+        description: "For artistic and experimental projects",
+        theme: "Rainbow Spectrum",
+        palette: Palette {
+            primary: hex(0x00c2d1),
+            secondary: hex(0xff5fa2),
+            accent: hex(0xffd23f),
+            background: hex(0x1a1033),
+        },
+        effect: Effect::Rainbow,
+        gradient: &[],
+        energy: 0.65,
+        positivity: 0.8,
+        music: &[
+            "Alternative, indie, experimental, world music",
+            "Art pop, trip-hop, film scores",
+            "Jazz fusion, shoegaze, field recordings",
+        ],
+        stacks: &[
+            "JavaScript, Three.js, WebGL, creative coding",
+            "Processing, p5.js, shaders, SVG",
+            "Svelte, Canvas, Web Audio, generative art",
+        ],
+        mottos: &[
+            "Art and code are one",
+            "Make the weird thing first",
+            "Every bug is an accidental feature",
+        ],
+        ascii_art: &[include_str!("../art/creative.txt")],
+        cow: None,
+        bubble: Bubble::Say,
+        timer_emoji: "🎨",
+        timer_message: "Let your creativity flow!",
+        break_art: include_str!("../art/creative.break.txt"),
+        blocked_sites: &["reddit.com", "twitter.com", "x.com", "facebook.com", "linkedin.com", "news.ycombinator.com"],
+        vscode_theme: "Andromeda",
+        vscode_theme_extension: Some("EliverLara.andromeda"),
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "productive",
+        description: "For corporate and efficient workflows",
+        theme: "Corporate Blue",
+        palette: Palette {
+            primary: hex(0x4f7cff),
+            secondary: hex(0x2c4a9e),
+            accent: hex(0xdfe7f5),
+            background: hex(0x0f1a33),
+        },
+        effect: Effect::Flat,
+        gradient: &[],
+        energy: 0.7,
+        positivity: 0.5,
+        music: &[
+            "Instrumental hip-hop, productivity playlists, white noise",
+            "Deep focus playlists, baroque, coffee shop ambience",
+            "Synthwave, steady techno, brown noise",
+        ],
+        stacks: &[
+            "Java, Spring Boot, MySQL, Kubernetes",
+            "C#, .NET, SQL Server, Azure",
+            "TypeScript, NestJS, PostgreSQL, Terraform",
+        ],
+        mottos: &[
+            "Efficiency is the ultimate form of beauty",
+            "Done is better than perfect",
+            "Inbox zero, merge queue empty",
+        ],
+        ascii_art: &[include_str!("../art/productive.txt")],
+        cow: None,
+        bubble: Bubble::Say,
+        timer_emoji: "💼",
+        timer_message: "Productivity mode: ON!",
+        break_art: include_str!("../art/productive.break.txt"),
+        blocked_sites: &["youtube.com", "reddit.com", "twitter.com", "x.com", "facebook.com", "instagram.com", "tiktok.com", "twitch.tv", "netflix.com", "news.ycombinator.com"],
+        vscode_theme: "Tomorrow Night Blue",
+        vscode_theme_extension: None,
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "nostalgic",
+        description: "For retro and classic computing",
+        theme: "Retro Sepia",
+        palette: Palette {
+            primary: hex(0xd9a45b),
+            secondary: hex(0x8a5a2b),
+            accent: hex(0xf2dcb3),
+            background: hex(0x2b1d0e),
+        },
+        effect: Effect::Flat,
+        gradient: &[],
+        energy: 0.35,
+        positivity: 0.4,
+        music: &[
+            "80s synthwave, classic rock, vinyl crackle",
+            "Chiptune, 90s trip-hop, cassette demos",
+            "Y2K pop, Winamp skins, MIDI covers",
+        ],
+        stacks: &[
+            "C++, OpenGL, legacy systems, retro computing",
+            "Perl, CGI, Apache, a guestbook",
+            "Pascal, BASIC, DOS batch files",
+        ],
+        mottos: &[
+            "The future is built on the past",
+            "Old code is proof someone shipped",
+            "They don't write them like they used to",
+        ],
+        ascii_art: &[include_str!("../art/nostalgic.txt")],
+        cow: None,
+        bubble: Bubble::Say,
+        timer_emoji: "📼",
+        timer_message: "Old school grind!",
+        break_art: include_str!("../art/nostalgic.break.txt"),
+        blocked_sites: &["tiktok.com", "instagram.com", "twitter.com", "x.com", "youtube.com"],
+        vscode_theme: "Kimbie Dark",
+        vscode_theme_extension: None,
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "adventurous",
+        description: "For cutting-edge and experimental tech",
+        theme: "Aurora Borealis",
+        palette: Palette {
+            primary: hex(0x3ee6b0),
+            secondary: hex(0x7a5cff),
+            accent: hex(0xb6ff6e),
+            background: hex(0x081a24),
+        },
+        effect: Effect::Vertical,
+        gradient: &[hex(0xb6ff6e), hex(0x3ee6b0), hex(0x7a5cff)],
+        energy: 0.8,
+        positivity: 0.7,
+        music: &[
+            "Epic orchestral, adventure soundtracks, tribal drums",
+            "Post-rock, world fusion, expedition soundtracks",
+            "Psychedelic rock, krautrock, space ambient",
+        ],
+        stacks: &[
+            "Rust, WebAssembly, blockchain, edge computing",
+            "Zig, embedded Rust, microcontrollers",
+            "Gleam, Nix, eBPF, something you found yesterday",
+        ],
+        mottos: &[
+            "Explore the unknown, build the impossible",
+            "Here be dragons, bring a debugger",
+            "The map ends where the fun starts",
+        ],
+        ascii_art: &[include_str!("../art/adventurous.txt")],
+        cow: None,
+        bubble: Bubble::Say,
+        timer_emoji: "🌌",
+        timer_message: "Explore new code worlds!",
+        break_art: include_str!("../art/adventurous.break.txt"),
+        blocked_sites: &["facebook.com", "instagram.com", "tiktok.com", "linkedin.com"],
+        vscode_theme: "Aurora X",
+        vscode_theme_extension: Some("marqu3s.aurora-x"),
+        vscode_zen: false,
+    },
+    BuiltinMood {
+        name: "zen",
+        description: "For minimal and mindful programming",
+        theme: "Minimalist White",
+        palette: Palette {
+            primary: hex(0xf5f5f0),
+            secondary: hex(0x9e9e96),
+            accent: hex(0xd8cfc0),
+            background: hex(0x1c1c1a),
+        },
+        effect: Effect::Flat,
+        gradient: &[],
+        energy: 0.1,
+        positivity: 0.6,
+        music: &[
+            "Meditation, zen gardens, silence, minimal ambient",
+            "Shakuhachi flute, singing bowls, soft rain",
+            "Drone ambient, piano minimalism, birdsong",
+        ],
+        stacks: &[
+            "Haskell, functional programming, pure functions",
+            "Lisp, a REPL, one small function at a time",
+            "Go standard library, no dependencies",
+        ],
+        mottos: &[
+            "Less is more, simplicity is complexity resolved",
+            "Breathe, then refactor",
+            "Delete more code than you write",
+        ],
+        ascii_art: &[include_str!("../art/zen.txt")],
+        cow: None,
+        bubble: Bubble::Think,
+        timer_emoji: "🧘",
+        timer_message: "Breathe and code.",
+        break_art: include_str!("../art/zen.break.txt"),
+        blocked_sites: &["youtube.com", "reddit.com", "twitter.com", "x.com", "facebook.com", "instagram.com", "tiktok.com", "twitch.tv", "netflix.com", "news.ycombinator.com", "linkedin.com", "cnn.com", "bbc.com"],
+        vscode_theme: "Default Light Modern",
+        vscode_theme_extension: None,
+        vscode_zen: true,
+    },
+];
+
+/// Synonyms, common misspellings and emoji for each built-in mood.
+const BUILTIN_ALIASES: &[(&str, &[&str])] = &[
+    ("focus", &["focused", "focs", "fokus", "deepwork", "concentrate", "flow", "🎯", "🧠"]),
+    ("chaotic", &["chaos", "chaotik", "wild", "unhinged", "crazy", "🌪", "🤪", "🤯"]),
+    ("sadboi", &["sad", "sadboy", "sadgirl", "sadgurl", "blue", "melancholy", "melancholic", "😢", "😭", "🌧"]),
+    ("energetic", &["energy", "energtic", "hype", "hyped", "pumped", "🔥", "⚡", "💪"]),
+    ("chill", &["calm", "chil", "relax", "relaxed", "cozy", "lazy", "😌", "🌿", "☕"]),
+    ("creative", &["creatvie", "artsy", "inspired", "art", "🎨", "🌈", "✨"]),
+    ("productive", &["productiv", "work", "grind", "busy", "efficient", "💼", "📈", "✅"]),
+    ("nostalgic", &["nostalgia", "retro", "vintage", "oldschool", "throwback", "📼", "🕹", "💾"]),
+    ("adventurous", &["adventure", "adventrous", "explore", "curious", "bold", "🚀", "🌌", "🧭"]),
+    ("zen", &["peaceful", "mindful", "meditate", "meditative", "minimal", "🧘", "☯", "🪷"]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What `lookup` made of `input`, as the mood names it came back with.
+    fn lookup(input: &str) -> (&'static str, Vec<String>) {
+        let registry = MoodRegistry::builtin();
+        match registry.lookup(input) {
+            Lookup::Found(mood) => ("found", vec![mood.name.clone()]),
+            Lookup::Ambiguous(names) => ("ambiguous", names.iter().map(|n| n.to_string()).collect()),
+            Lookup::Unknown(names) => ("unknown", names.iter().map(|n| n.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn edit_distance_counts_characters() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("zen", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("focus", "focsu"), 2);
+        // Not bytes: each of these is three bytes long
+        assert_eq!(edit_distance("ぁぁ", "ぁ"), 1);
+        assert_eq!(edit_distance("🎯", "🧠"), 1);
+    }
+
+    #[test]
+    fn finds_names_and_aliases_however_they_are_written() {
+        let spellings = ["focus", "FOCUS", " Focus ", "focused", "deepwork", "deep-work", "Deep Work", "deep_work", "🎯"];
+        for input in spellings {
+            assert_eq!(lookup(input), ("found", vec!["focus".to_string()]), "{:?}", input);
+        }
+        // With and without the emoji variation selector
+        assert_eq!(lookup("☯\u{fe0f}"), ("found", vec!["zen".to_string()]));
+        assert_eq!(lookup("☯"), ("found", vec!["zen".to_string()]));
+    }
+
+    #[test]
+    fn a_prefix_finds_the_one_mood_it_starts() {
+        assert_eq!(lookup("adv"), ("found", vec!["adventurous".to_string()]));
+        assert_eq!(lookup("Prod"), ("found", vec!["productive".to_string()]));
+    }
+
+    #[test]
+    fn a_shared_prefix_is_ambiguous() {
+        assert_eq!(lookup("ch"), ("ambiguous", vec!["chaotic".to_string(), "chill".to_string()]));
+        assert_eq!(
+            lookup("c"),
+            ("ambiguous", vec!["chaotic".to_string(), "chill".to_string(), "creative".to_string()])
+        );
+    }
+
+    #[test]
+    fn typos_get_suggestions() {
+        assert_eq!(lookup("fcous"), ("unknown", vec!["focus".to_string()]));
+        // Close to the alias "sad", so it suggests the mood behind it
+        assert_eq!(lookup("sadd"), ("unknown", vec!["sadboi".to_string()]));
+        assert_eq!(lookup("xyzzyxyzzy"), ("unknown", Vec::new()));
+    }
+}
@@ -0,0 +1,125 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Machine-readable formats for `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Toml,
+    /// One `key=value` pair per line, newlines escaped as `\n`
+    Plain,
+}
+
+/// Error printed instead of a report when the request can't be served, so
+/// scripts get something they can parse alongside the non-zero exit code.
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput<'a> {
+    pub error: ErrorDetails<'a>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorDetails<'a> {
+    pub code: &'a str,
+    pub message: String,
+    pub mood: &'a str,
+    /// Closest matches for a typo, or every candidate for an ambiguous prefix
+    pub suggestions: Vec<&'a str>,
+    pub available_moods: Vec<&'a str>,
+}
+
+pub fn render<T: Serialize>(value: &T, format: OutputFormat) -> String {
+    let rendered = match format {
+        OutputFormat::Json => serde_json::to_string_pretty(value).expect("JSON serialization failed"),
+        OutputFormat::Yaml => serde_yaml::to_string(value).expect("YAML serialization failed"),
+        OutputFormat::Toml => toml::to_string(value).expect("TOML serialization failed"),
+        OutputFormat::Plain => {
+            let value = serde_json::to_value(value).expect("serialization failed");
+            let mut lines = Vec::new();
+            flatten_plain("", &value, &mut lines);
+            lines.join("\n")
+        }
+    };
+    rendered.trim_end().to_string()
+}
+
+/// Nested objects become dotted keys (`error.code=...`), lists of plain
+/// values are joined with commas, and lists of objects or lists are numbered
+/// (`explanation.matches.0.word=...`).
+fn flatten_plain(prefix: &str, value: &Value, lines: &mut Vec<String>) {
+    let child = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten_plain(&child(key), value, lines);
+            }
+        }
+        Value::Array(items) if items.iter().any(|item| item.is_object() || item.is_array()) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten_plain(&child(&i.to_string()), item, lines);
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(plain_scalar).collect();
+            lines.push(format!("{}={}", prefix, items.join(",")));
+        }
+        _ => lines.push(format!("{}={}", prefix, plain_scalar(value))),
+    }
+}
+
+fn plain_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.replace('\\', "\\\\").replace('\n', "\\n"),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plain(value: Value) -> Vec<String> {
+        let mut lines = Vec::new();
+        flatten_plain("", &value, &mut lines);
+        lines
+    }
+
+    #[test]
+    fn plain_lists_of_objects_get_numbered_keys() {
+        let value = json!({
+            "mood": "sadboi",
+            "suggestions": ["zen", "chill"],
+            "explanation": {
+                "matches": [
+                    {"word": "tired", "mood": "sadboi"},
+                    {"word": "ship", "mood": "productive"},
+                ],
+            },
+        });
+        assert_eq!(
+            plain(value),
+            [
+                "mood=sadboi",
+                "suggestions=zen,chill",
+                "explanation.matches.0.word=tired",
+                "explanation.matches.0.mood=sadboi",
+                "explanation.matches.1.word=ship",
+                "explanation.matches.1.mood=productive",
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_escapes_newlines() {
+        assert_eq!(plain(json!({"art": "a\\b\nc", "empty": []})), ["art=a\\\\b\\nc", "empty="]);
+    }
+}
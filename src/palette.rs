@@ -0,0 +1,313 @@
+use clap::ValueEnum;
+use colored::{control::SHOULD_COLORIZE, Color};
+use serde::{Serialize, Serializer};
+use std::{
+    env,
+    io::{self, IsTerminal},
+    sync::OnceLock,
+};
+
+/// Whether to color output, for `--color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Color a terminal, unless `NO_COLOR` is set; `CLICOLOR_FORCE` colors
+    /// anything
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(self) -> bool {
+        // https://no-color.org: any non-empty value, even "0"
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let force = env::var_os("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0");
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto if no_color => false,
+            ColorChoice::Auto if force => true,
+            ColorChoice::Auto => io::stdout().is_terminal() && !env::var("TERM").is_ok_and(|term| term == "dumb"),
+        }
+    }
+
+    /// Make every colored string and [`fg`]-style painting follow this
+    /// choice for the rest of the run.
+    pub fn apply(self) {
+        colored::control::set_override(self.enabled());
+    }
+}
+
+/// An exact color, written "#rrggbb" in `moods.toml` and `--format` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// `hex(0x1e90ff)`, for the built-in palettes.
+pub const fn hex(rgb: u32) -> Rgb {
+    Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+}
+
+impl Rgb {
+    const BLACK: Rgb = Rgb(0, 0, 0);
+    const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// A color name understood by `colored` ("blue", "bright magenta", ...)
+    /// or "#rrggbb".
+    pub fn parse(color: &str) -> Option<Rgb> {
+        match color.strip_prefix('#') {
+            Some(hex) if hex.len() == 6 && hex.is_ascii() => {
+                let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+                Some(Rgb(channel(0)?, channel(2)?, channel(4)?))
+            }
+            Some(_) => None,
+            None => color.parse::<Color>().ok().map(Rgb::from),
+        }
+    }
+
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+
+    /// Weighted average of `colors`, channel by channel.
+    pub fn mix(colors: &[(Rgb, f64)]) -> Rgb {
+        let total: f64 = colors.iter().map(|(_, w)| w).sum();
+        let channel = |pick: fn(Rgb) -> u8| {
+            let mixed: f64 = colors.iter().map(|(c, w)| f64::from(pick(*c)) * w).sum();
+            (mixed / total).round() as u8
+        };
+        Rgb(channel(|c| c.0), channel(|c| c.1), channel(|c| c.2))
+    }
+
+    fn distance(self, other: Rgb) -> u32 {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+        d(self.0, other.0) + d(self.1, other.1) + d(self.2, other.2)
+    }
+}
+
+/// Roughly what xterm shows for the 16 named colors.
+impl From<Color> for Rgb {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Black => Rgb(0, 0, 0),
+            Color::Red => Rgb(205, 0, 0),
+            Color::Green => Rgb(0, 205, 0),
+            Color::Yellow => Rgb(205, 205, 0),
+            Color::Blue => Rgb(0, 0, 238),
+            Color::Magenta => Rgb(205, 0, 205),
+            Color::Cyan => Rgb(0, 205, 205),
+            Color::White => Rgb(229, 229, 229),
+            Color::BrightBlack => Rgb(127, 127, 127),
+            Color::BrightRed => Rgb(255, 0, 0),
+            Color::BrightGreen => Rgb(0, 255, 0),
+            Color::BrightYellow => Rgb(255, 255, 0),
+            Color::BrightBlue => Rgb(92, 92, 255),
+            Color::BrightMagenta => Rgb(255, 0, 255),
+            Color::BrightCyan => Rgb(0, 255, 255),
+            Color::BrightWhite => Rgb(255, 255, 255),
+            Color::TrueColor { r, g, b } => Rgb(r, g, b),
+        }
+    }
+}
+
+impl Serialize for Rgb {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.hex())
+    }
+}
+
+/// The colors a mood's report, boxes and art are drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Palette {
+    /// Values and art
+    pub primary: Rgb,
+    /// Box borders
+    pub secondary: Rgb,
+    /// Labels and titles
+    pub accent: Rgb,
+    /// Behind titles
+    pub background: Rgb,
+}
+
+impl Palette {
+    /// A palette built around one color, for moods that only set `color`:
+    /// a darker shade for borders, a lighter one for labels and a near-black
+    /// tint behind titles.
+    pub fn from_color(color: Rgb) -> Palette {
+        Palette {
+            primary: color,
+            secondary: Rgb::mix(&[(color, 0.65), (Rgb::BLACK, 0.35)]),
+            accent: Rgb::mix(&[(color, 0.55), (Rgb::WHITE, 0.45)]),
+            background: Rgb::mix(&[(color, 0.15), (Rgb::BLACK, 0.85)]),
+        }
+    }
+
+    /// Weighted average of each of the four colors.
+    pub fn mix(palettes: &[(Palette, f64)]) -> Palette {
+        let mix = |pick: fn(&Palette) -> Rgb| Rgb::mix(&palettes.iter().map(|(p, w)| (pick(p), *w)).collect::<Vec<_>>());
+        Palette {
+            primary: mix(|p| p.primary),
+            secondary: mix(|p| p.secondary),
+            accent: mix(|p| p.accent),
+            background: mix(|p| p.background),
+        }
+    }
+
+    /// Four blocks showing the palette, as printed next to the theme name.
+    pub fn swatches(&self) -> String {
+        [self.primary, self.secondary, self.accent, self.background]
+            .map(|color| fg("██", color))
+            .join(" ")
+    }
+}
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Depth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+/// Read from `COLORTERM` ("truecolor" or "24bit") and `TERM` ("...256color"),
+/// the way most terminals advertise it.
+pub fn depth() -> Depth {
+    static DEPTH: OnceLock<Depth> = OnceLock::new();
+    *DEPTH.get_or_init(|| {
+        let colorterm = env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        let term = env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            Depth::TrueColor
+        } else if term.contains("256") {
+            Depth::Ansi256
+        } else {
+            Depth::Ansi16
+        }
+    })
+}
+
+/// `text` in `color`, as close as the terminal can get.
+pub fn fg(text: &str, color: Rgb) -> String {
+    paint(text, &[code(color, Layer::Foreground)])
+}
+
+/// `text` in bold `color`.
+pub fn bold(text: &str, color: Rgb) -> String {
+    paint(text, &["1".to_string(), code(color, Layer::Foreground)])
+}
+
+/// `text` in bold `color` on `background`, for titles.
+pub fn title(text: &str, color: Rgb, background: Rgb) -> String {
+    paint(
+        text,
+        &["1".to_string(), code(color, Layer::Foreground), code(background, Layer::Background)],
+    )
+}
+
+/// Each character in its own color, for gradients. A new escape only
+/// starts when the color the terminal will show changes, which on 16 colors
+/// is rarely; spaces stay in whatever run they fall in.
+pub fn fg_runs(chars: impl Iterator<Item = (char, Rgb)>) -> String {
+    if !SHOULD_COLORIZE.should_colorize() {
+        return chars.map(|(c, _)| c).collect();
+    }
+    let mut out = String::new();
+    let mut current: Option<String> = None;
+    for (c, color) in chars {
+        if c != ' ' {
+            let code = code(color, Layer::Foreground);
+            if current.as_ref() != Some(&code) {
+                out.push_str(&format!("\x1b[{}m", code));
+                current = Some(code);
+            }
+        }
+        out.push(c);
+    }
+    if current.is_some() {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+fn paint(text: &str, codes: &[String]) -> String {
+    if text.is_empty() || !SHOULD_COLORIZE.should_colorize() {
+        return text.to_string();
+    }
+    format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+}
+
+#[derive(Clone, Copy)]
+enum Layer {
+    Foreground,
+    Background,
+}
+
+/// The SGR parameters for `color` at the terminal's depth.
+fn code(color: Rgb, layer: Layer) -> String {
+    let (extended, base, bright) = match layer {
+        Layer::Foreground => (38, 30, 90),
+        Layer::Background => (48, 40, 100),
+    };
+    match depth() {
+        Depth::TrueColor => format!("{};2;{};{};{}", extended, color.0, color.1, color.2),
+        Depth::Ansi256 => format!("{};5;{}", extended, ansi256(color)),
+        Depth::Ansi16 => {
+            let index = ansi16(color);
+            if index < 8 {
+                (base + index).to_string()
+            } else {
+                (bright + index - 8).to_string()
+            }
+        }
+    }
+}
+
+/// Levels of each channel in xterm's 6×6×6 color cube.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The closest of the 240 cube and grayscale entries of the 256-color table.
+fn ansi256(color: Rgb) -> u8 {
+    let level = |channel: u8| {
+        (0..6)
+            .min_by_key(|&i| (i32::from(CUBE[i]) - i32::from(channel)).abs())
+            .expect("the cube has levels")
+    };
+    let (r, g, b) = (level(color.0), level(color.1), level(color.2));
+    let cube = Rgb(CUBE[r], CUBE[g], CUBE[b]);
+
+    let average = (u32::from(color.0) + u32::from(color.1) + u32::from(color.2)) / 3;
+    let step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_value = 8 + step * 10;
+    let gray = Rgb(gray_value, gray_value, gray_value);
+
+    if gray.distance(color) < cube.distance(color) {
+        232 + step
+    } else {
+        16 + 36 * r as u8 + 6 * g as u8 + b as u8
+    }
+}
+
+/// Index into the 16 named colors, normal 0-7 then bright 8-15.
+fn ansi16(color: Rgb) -> u8 {
+    const NAMED: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+        Color::BrightBlack,
+        Color::BrightRed,
+        Color::BrightGreen,
+        Color::BrightYellow,
+        Color::BrightBlue,
+        Color::BrightMagenta,
+        Color::BrightCyan,
+        Color::BrightWhite,
+    ];
+    (0..16)
+        .min_by_key(|&i| Rgb::from(NAMED[i]).distance(color))
+        .expect("there are 16 named colors") as u8
+}
@@ -0,0 +1,206 @@
+use crate::palette::{self, Palette};
+use colored::*;
+use crossterm::terminal;
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Outer width of header boxes, when the terminal is wide enough.
+const BOX_WIDTH: usize = 43;
+
+/// Narrowest anything is laid out for, however small the terminal claims to be.
+const MIN_WIDTH: usize = 20;
+
+/// Width assumed when output isn't a terminal and `COLUMNS` isn't set.
+const DEFAULT_WIDTH: usize = 80;
+
+/// What a panel is drawn in: one named color for borders and title, with the
+/// usual yellow labels and green values, or a mood's palette.
+#[derive(Debug, Clone, Copy)]
+pub enum Colors {
+    Named(Color),
+    Palette(Palette),
+}
+
+impl Colors {
+    fn border(&self, text: &str) -> String {
+        match self {
+            Colors::Named(color) => text.color(*color).to_string(),
+            Colors::Palette(p) => palette::fg(text, p.secondary),
+        }
+    }
+
+    fn title(&self, text: &str) -> String {
+        match self {
+            Colors::Named(color) => text.color(*color).to_string(),
+            Colors::Palette(p) => palette::title(text, p.accent, p.background),
+        }
+    }
+
+    fn label(&self, text: &str) -> String {
+        match self {
+            Colors::Named(_) => text.yellow().to_string(),
+            Colors::Palette(p) => palette::fg(text, p.accent),
+        }
+    }
+
+    fn value(&self, text: &str) -> String {
+        match self {
+            Colors::Named(_) => text.green().to_string(),
+            Colors::Palette(p) => palette::fg(text, p.primary),
+        }
+    }
+}
+
+/// Columns available for output: the terminal's width, or `COLUMNS` (then
+/// 80) when writing to a pipe or file.
+pub fn terminal_width() -> usize {
+    let on_terminal = io::stdout()
+        .is_terminal()
+        .then(terminal::size)
+        .and_then(Result::ok)
+        .map(|(columns, _)| usize::from(columns));
+    on_terminal
+        .or_else(|| env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+        .max(MIN_WIDTH)
+}
+
+/// Terminal columns `text` takes up: emoji and CJK count two, combining
+/// marks none, and color escapes are skipped.
+pub fn width(text: &str) -> usize {
+    if !text.contains('\x1b') {
+        return text.width();
+    }
+    let mut visible = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequences end with a byte in @..~, e.g. the "m" of "\x1b[1;32m"
+            chars.by_ref().skip(1).find(|c| ('@'..='~').contains(c));
+        } else {
+            visible.push(c);
+        }
+    }
+    visible.width()
+}
+
+/// Cut `text` to at most `max` columns, marking the cut with an ellipsis.
+pub fn fit(text: &str, max: usize) -> String {
+    if text.width() <= max {
+        return text.to_string();
+    }
+    let mut cut = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > max {
+            break;
+        }
+        cut.push(c);
+        used += w;
+    }
+    cut.push('…');
+    cut
+}
+
+/// Greedy word wrap; words longer than the width are cut with [`fit`].
+pub fn wrap(text: &str, max: usize) -> Vec<String> {
+    let max = max.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let needed = line.width() + word.width() + usize::from(!line.is_empty());
+        if !line.is_empty() && needed > max {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines.into_iter().map(|l| fit(&l, max)).collect()
+}
+
+/// `text` padded with spaces on both sides to `columns`.
+fn center(text: &str, columns: usize) -> String {
+    let spare = columns.saturating_sub(width(text));
+    format!("{}{}{}", " ".repeat(spare / 2), text, " ".repeat(spare - spare / 2))
+}
+
+/// A three-line box around a centered title, narrowed to fit the terminal.
+pub fn header(title: &str, colors: Colors) -> Vec<String> {
+    let inner = BOX_WIDTH.min(terminal_width()) - 2;
+    let rule = "─".repeat(inner);
+    vec![
+        colors.border(&format!("╭{}╮", rule)),
+        format!("{}{}{}", colors.border("│"), colors.title(&center(&fit(title, inner), inner)), colors.border("│")),
+        colors.border(&format!("╰{}╯", rule)),
+    ]
+}
+
+/// [`header`] without color, for painting as a whole.
+pub fn frame(title: &str) -> Vec<String> {
+    let inner = BOX_WIDTH.min(terminal_width()) - 2;
+    let rule = "─".repeat(inner);
+    vec![
+        format!("╭{}╮", rule),
+        format!("│{}│", center(&fit(title, inner), inner)),
+        format!("╰{}╯", rule),
+    ]
+}
+
+pub fn print_header(title: &str, colors: Colors) {
+    for line in header(title, colors) {
+        println!("{}", line);
+    }
+}
+
+/// "🎵 Music: ..." wrapped to `max` columns, with continuation lines
+/// indented to start under the value.
+pub fn field(icon: &str, label: &str, value: &str, colors: Colors, max: usize) -> Vec<String> {
+    field_with(icon, label, value, colors, max, |parts| parts.iter().map(|part| colors.value(part)).collect())
+}
+
+/// [`field`] with the wrapped value lines colored by `paint`, for effects
+/// that span several lines.
+pub fn field_with(
+    icon: &str,
+    label: &str,
+    value: &str,
+    colors: Colors,
+    max: usize,
+    paint: impl FnOnce(&[String]) -> Vec<String>,
+) -> Vec<String> {
+    let indent = width(&format!("{} {}: ", icon, label));
+    paint(&wrap(value, max.saturating_sub(indent)))
+        .into_iter()
+        .enumerate()
+        .map(|(n, part)| {
+            if n == 0 {
+                format!("{} {}: {}", icon, colors.label(label), part)
+            } else {
+                format!("{}{}", " ".repeat(indent), part)
+            }
+        })
+        .collect()
+}
+
+/// [`field`] at the terminal's width.
+pub fn print_field(icon: &str, label: &str, value: &str, colors: Colors) {
+    for line in field(icon, label, value, colors, terminal_width()) {
+        println!("{}", line);
+    }
+}
+
+/// Top edge of a `columns`-wide frame with the title set into it:
+/// "╭─ title ────╮".
+pub fn top_rule(title: &str, columns: usize) -> String {
+    let title = fit(title, columns.saturating_sub(4));
+    format!("╭─{}{}─╮", title, "─".repeat(columns.saturating_sub(width(&title) + 4)))
+}
@@ -0,0 +1,21 @@
+use std::{env, path::PathBuf};
+
+/// Resolve an XDG base directory, falling back to `$HOME/<fallback>` when the
+/// variable is unset or empty, and append our own `vibe` subdirectory.
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    let base = match env::var_os(var) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(fallback),
+    };
+    Some(base.join("vibe"))
+}
+
+/// `$XDG_CONFIG_HOME/vibe` (usually `~/.config/vibe`)
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_DATA_HOME/vibe` (usually `~/.local/share/vibe`)
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
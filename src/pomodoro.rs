@@ -0,0 +1,256 @@
+use crate::{
+    panel,
+    tui::{self, RawTerminal},
+};
+use colored::*;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use std::{
+    io::{self, IsTerminal, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+/// Layout of a pomodoro session: `cycles` work intervals, each followed by a
+/// short break, except every `long_break_every`th which earns a long one.
+#[derive(Debug, Clone, Copy)]
+pub struct Plan {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub long_break_every: u32,
+    pub cycles: u32,
+}
+
+impl Plan {
+    /// The break that follows work interval `cycle` (counting from 1).
+    pub fn break_after(&self, cycle: u32) -> Phase {
+        if cycle.is_multiple_of(self.long_break_every) {
+            Phase::LongBreak
+        } else {
+            Phase::ShortBreak
+        }
+    }
+
+    pub fn minutes(&self, phase: Phase) -> u32 {
+        match phase {
+            Phase::Work => self.work_minutes,
+            Phase::ShortBreak => self.short_break_minutes,
+            Phase::LongBreak => self.long_break_minutes,
+        }
+    }
+}
+
+/// What actually happened during a session, for the report at the end.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub completed_pomodoros: u32,
+    /// Pomodoros cut short with `s`, which don't count as completed
+    pub skipped_pomodoros: u32,
+    pub focused_seconds: u64,
+    pub short_breaks: u32,
+    pub long_breaks: u32,
+    pub break_seconds: u64,
+}
+
+impl Summary {
+    pub fn record(&mut self, phase: Phase, countdown: Countdown) {
+        let seconds = countdown.elapsed_seconds;
+        match phase {
+            Phase::Work => {
+                if countdown.outcome == Outcome::Skipped {
+                    self.skipped_pomodoros += 1;
+                } else {
+                    self.completed_pomodoros += 1;
+                }
+                self.focused_seconds += seconds;
+            }
+            Phase::ShortBreak => {
+                self.short_breaks += 1;
+                self.break_seconds += seconds;
+            }
+            Phase::LongBreak => {
+                self.long_breaks += 1;
+                self.break_seconds += seconds;
+            }
+        }
+    }
+}
+
+/// How a countdown ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Finished,
+    /// The user pressed `s` to move on to the next phase early
+    Skipped,
+    /// The user confirmed `q` or pressed Ctrl+C
+    Quit,
+}
+
+/// Result of one countdown: how it ended and how many seconds actually ran
+/// (pauses excluded).
+#[derive(Debug, Clone, Copy)]
+pub struct Countdown {
+    pub outcome: Outcome,
+    pub elapsed_seconds: u64,
+}
+
+const EXTEND_BY: Duration = Duration::from_secs(5 * 60);
+const KEY_HINTS: &str = "p pause · s skip · + 5 min · q quit";
+
+/// Draws "mm:ss" in big letters, one string per row.
+pub type BigDigits<'a> = &'a dyn Fn(&str) -> Vec<String>;
+
+/// Count `seconds` down on a single line. On a terminal the user gets a footer
+/// of single-key controls, and the time is drawn in `big` digits above the
+/// line when they fit; otherwise it just sleeps until Ctrl+C.
+pub fn countdown(seconds: u64, emoji: &str, label: &str, big: Option<BigDigits>) -> Countdown {
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        if let Ok(countdown) = interactive_countdown(seconds, emoji, label, big) {
+            return countdown;
+        }
+    }
+
+    let mut remaining = seconds;
+    while remaining > 0 {
+        print!("\r{} {:02}:{:02} {}... ", emoji, remaining / 60, remaining % 60, label);
+        io::stdout().flush().unwrap();
+        thread::sleep(Duration::from_secs(1));
+        remaining -= 1;
+    }
+    Countdown {
+        outcome: Outcome::Finished,
+        elapsed_seconds: seconds,
+    }
+}
+
+fn interactive_countdown(seconds: u64, emoji: &str, label: &str, big: Option<BigDigits>) -> io::Result<Countdown> {
+    let _terminal = RawTerminal::enter(false)?;
+    let mut stdout = io::stdout();
+    let (columns, rows) = terminal::size()?;
+
+    // Big digits need room for their rows as well as the status line and
+    // footer
+    let big = big.filter(|render| render("00:00").len() + 2 < usize::from(rows));
+    let big_rows = big.map_or(0, |render| render("00:00").len()) as u16;
+
+    // Make sure there are lines below the top one for the rest, even when
+    // we're sitting on the last row of the terminal
+    queue!(stdout, Print("\r\n".repeat(usize::from(big_rows) + 1)), cursor::MoveToPreviousLine(big_rows + 1))?;
+
+    let mut remaining = Duration::from_secs(seconds);
+    let mut elapsed = Duration::ZERO;
+    let mut paused = false;
+    let mut confirming_quit = false;
+    let mut last_tick = Instant::now();
+
+    let outcome = loop {
+        let now = Instant::now();
+        if !paused {
+            let step = now - last_tick;
+            elapsed += step;
+            remaining = remaining.saturating_sub(step);
+        }
+        last_tick = now;
+        if remaining.is_zero() {
+            break Outcome::Finished;
+        }
+
+        // Round up so "00:00" only ever shows once the time is really up
+        let shown = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let time = format!("{:02}:{:02}", shown / 60, shown % 60);
+        let status = if paused {
+            format!("⏸️  {} paused", time)
+        } else {
+            format!("{} {} {}...", emoji, time, label)
+        };
+        let footer = if confirming_quit {
+            format!("{}", "Quit this session? y/n".yellow())
+        } else {
+            format!("{}", KEY_HINTS.dimmed())
+        };
+        queue!(stdout, cursor::MoveToColumn(0))?;
+        if let Some(render) = big {
+            let digits = render(&time);
+            // A long timer can outgrow a narrow terminal; leave the rows blank
+            let fits = digits.iter().all(|line| panel::width(line) < usize::from(columns));
+            for line in digits {
+                queue!(
+                    stdout,
+                    terminal::Clear(ClearType::CurrentLine),
+                    Print(if fits { line } else { String::new() }),
+                    cursor::MoveToNextLine(1),
+                )?;
+            }
+        }
+        queue!(
+            stdout,
+            terminal::Clear(ClearType::CurrentLine),
+            Print(status),
+            cursor::MoveToNextLine(1),
+            terminal::Clear(ClearType::CurrentLine),
+            Print(footer),
+            cursor::MoveToPreviousLine(big_rows + 1),
+        )?;
+        stdout.flush()?;
+
+        if !event::poll(Duration::from_millis(200))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if tui::is_interrupt(&key) {
+            break Outcome::Quit;
+        }
+        if confirming_quit {
+            if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                break Outcome::Quit;
+            }
+            confirming_quit = false;
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('p') | KeyCode::Char(' ') => paused = !paused,
+            KeyCode::Char('s') => break Outcome::Skipped,
+            KeyCode::Char('+') => remaining += EXTEND_BY,
+            KeyCode::Char('q') => confirming_quit = true,
+            _ => {}
+        }
+    };
+
+    // Clear the digits, status line and footer and leave the cursor where
+    // they started, so the caller's "\r..." message takes their place
+    queue!(stdout, cursor::MoveToColumn(0), terminal::Clear(ClearType::FromCursorDown))?;
+    stdout.flush()?;
+
+    Ok(Countdown {
+        outcome,
+        elapsed_seconds: elapsed.as_secs(),
+    })
+}
+
+/// "1h 05m", "25m" or "40s", for summaries.
+pub fn format_duration(seconds: u64) -> String {
+    let (hours, minutes) = (seconds / 3600, seconds % 3600 / 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", seconds),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {:02}m", h, m),
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    history::{Entry, Event},
+    moods::{MoodRegistry, Part, Variant},
+    panel::{self, Colors},
+    schedule::Weight,
+};
+use colored::*;
+use std::collections::HashMap;
+
+/// Most liked variants listed by `vibe prefs`.
+const MAX_VARIANTS: usize = 8;
+
+/// What a mood or variant has earned from ratings, like one arm of a
+/// multi-armed bandit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Arm {
+    pub ratings: u32,
+    /// Sum of the scores mapped onto 0 (1 star) to 1 (5 stars)
+    reward: f64,
+}
+
+impl Arm {
+    fn rate(&mut self, score: u8) {
+        self.ratings += 1;
+        self.reward += f64::from(score.clamp(1, 5) - 1) / 4.0;
+    }
+
+    /// The expected reward under a uniform prior, doubled so an unrated arm
+    /// weighs 1, a loved one tends towards 2 and a disliked one towards 0.
+    /// Every rating counts a little less than the one before, so a new
+    /// opinion can still move an arm rated many times.
+    pub fn multiplier(&self) -> f64 {
+        2.0 * (1.0 + self.reward) / (2.0 + f64::from(self.ratings))
+    }
+
+    pub fn average_score(&self) -> f64 {
+        1.0 + 4.0 * self.reward / f64::from(self.ratings.max(1))
+    }
+}
+
+/// Preferences learned from `vibe rate`, biasing random moods and variants.
+#[derive(Debug, Default)]
+pub struct Prefs {
+    moods: HashMap<String, Arm>,
+    variants: HashMap<(String, Part, usize), Arm>,
+}
+
+impl Prefs {
+    /// Tally every rating in the history. Rating the same entry again
+    /// replaces the earlier score.
+    pub fn from_history(entries: &[Entry]) -> Self {
+        let mut latest: HashMap<u64, (&str, Option<Variant>, u8)> = HashMap::new();
+        for entry in entries {
+            if let Event::Rating { entry: rated, score, .. } = entry.event {
+                latest.insert(rated, (&entry.mood, entry.variant, score));
+            }
+        }
+
+        let mut prefs = Prefs::default();
+        for (mood, variant, score) in latest.into_values() {
+            prefs.moods.entry(mood.to_string()).or_default().rate(score);
+            if let Some(variant) = variant {
+                for part in Part::ALL {
+                    let key = (mood.to_string(), part, variant.get(part));
+                    prefs.variants.entry(key).or_default().rate(score);
+                }
+            }
+        }
+        prefs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moods.is_empty()
+    }
+
+    pub fn mood_weight(&self, mood: &str) -> f64 {
+        self.moods.get(mood).map_or(1.0, Arm::multiplier)
+    }
+
+    pub fn variant_weight(&self, mood: &str, part: Part, index: usize) -> f64 {
+        self.variants
+            .get(&(mood.to_string(), part, index))
+            .map_or(1.0, Arm::multiplier)
+    }
+
+    /// Scale schedule weights by how each mood has been rated, noting it as
+    /// a reason for `--why`.
+    pub fn adjust(&self, weights: &mut [Weight]) {
+        for weight in weights.iter_mut() {
+            let multiplier = self.mood_weight(&weight.mood);
+            if multiplier != 1.0 {
+                weight.weight *= multiplier;
+                weight.reasons.push(("your ratings".to_string(), (multiplier * 100.0).round() / 100.0));
+            }
+        }
+        weights.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    }
+}
+
+/// "1" to "5", or a thumb: "up"/"+" is a 5 and "down"/"-" a 1.
+pub fn parse_score(value: &str) -> Result<u8, String> {
+    match value.trim().to_lowercase().as_str() {
+        "up" | "+" | "👍" => Ok(5),
+        "down" | "-" | "👎" => Ok(1),
+        other => match other.parse::<u8>() {
+            Ok(score @ 1..=5) => Ok(score),
+            _ => Err(format!("'{}' is not a rating: use 1-5, up or down", value)),
+        },
+    }
+}
+
+pub fn stars(score: u8) -> String {
+    let score = usize::from(score.clamp(1, 5));
+    format!("{}{}", "★".repeat(score), "☆".repeat(5 - score))
+}
+
+/// The learned weights behind `vibe prefs`.
+pub fn print_report(prefs: &Prefs, registry: &MoodRegistry) {
+    panel::print_header("LEARNED PREFERENCES", Colors::Named(Color::Cyan));
+    if prefs.is_empty() {
+        println!("{}", "No ratings yet. Rate a vibe with `vibe rate 1-5` (or up/down)".dimmed());
+        return;
+    }
+
+    let mut moods: Vec<(&String, &Arm)> = prefs.moods.iter().collect();
+    moods.sort_by(|a, b| b.1.multiplier().total_cmp(&a.1.multiplier()).then(a.0.cmp(b.0)));
+    let width = moods.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    println!("{}", "🎯 Moods".yellow());
+    for (name, arm) in &moods {
+        let filled = (arm.multiplier() * 10.0).round() as usize;
+        println!(
+            "   {} {}{} ×{:.2}  {}",
+            format!("{:<width$}", name, width = width).cyan(),
+            "█".repeat(filled).green(),
+            "░".repeat(20 - filled).dimmed(),
+            arm.multiplier(),
+            format!("avg {:.1} from {} {}", arm.average_score(), arm.ratings, plural(arm.ratings)).dimmed()
+        );
+    }
+    let unrated = registry.names().into_iter().filter(|name| !prefs.moods.contains_key(*name)).count();
+    if unrated > 0 {
+        let moods = if unrated == 1 { "mood" } else { "moods" };
+        println!("   {}", format!("{} unrated {} keep ×1.00", unrated, moods).dimmed());
+    }
+
+    let mut variants: Vec<(String, f64)> = prefs
+        .variants
+        .iter()
+        .filter_map(|((mood, part, index), arm)| {
+            let found = registry.get(mood)?;
+            let options = match part {
+                Part::Music => &found.variants.music,
+                Part::Stack => &found.variants.stacks,
+                Part::Motto => &found.variants.mottos,
+                Part::Art => return None,
+            };
+            // A single option has nothing to be weighed against
+            if options.len() < 2 {
+                return None;
+            }
+            let text = options.get(*index)?.lines().next().unwrap_or_default();
+            Some((format!("{} {}: {}", mood, part.label(), text), arm.multiplier()))
+        })
+        .collect();
+    variants.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    if !variants.is_empty() {
+        println!();
+        println!("{}", "🎲 Variants".yellow());
+        for (label, multiplier) in variants.iter().take(MAX_VARIANTS) {
+            println!("   {} {}", panel::fit(label, 60), format!("×{:.2}", multiplier).dimmed());
+        }
+    }
+    println!();
+    println!("{}", "Forget them with `vibe prefs --reset` (or `--reset --mood NAME`)".dimmed());
+}
+
+fn plural(ratings: u32) -> &'static str {
+    if ratings == 1 { "rating" } else { "ratings" }
+}
@@ -0,0 +1,249 @@
+use crate::{
+    moods::{Lookup, MoodRegistry},
+    paths,
+};
+use chrono::{Datelike, NaiveDateTime, Timelike, Weekday};
+use colored::*;
+use rand::{distributions::WeightedIndex, prelude::*};
+use serde::Deserialize;
+use std::fs;
+
+/// Moods a rule favors (or, below 1, discourages) at certain hours or days.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    name: String,
+    moods: Vec<String>,
+    /// "6-12": from 06:00 up to 12:00. "22-6" wraps past midnight
+    #[serde(default)]
+    hours: Option<String>,
+    /// "mon".."sun", "weekdays" or "weekend"
+    #[serde(default)]
+    days: Vec<String>,
+    /// Multiplies the chance of each of `moods`; 0 rules them out
+    weight: f64,
+}
+
+/// How random moods are weighted: the built-in rules, which are on by
+/// default and turned off with `use_builtin = false`, plus any from
+/// `$XDG_CONFIG_HOME/vibe/schedule.toml`.
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ScheduleFile {
+    use_builtin: bool,
+    rules: Vec<Rule>,
+}
+
+impl Default for ScheduleFile {
+    fn default() -> Self {
+        ScheduleFile {
+            use_builtin: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// One mood's chance, and the rules that shaped it.
+#[derive(Debug)]
+pub struct Weight {
+    pub mood: String,
+    pub weight: f64,
+    pub reasons: Vec<(String, f64)>,
+}
+
+impl Schedule {
+    pub fn builtin() -> Self {
+        Schedule {
+            rules: BUILTIN_RULES.iter().map(BuiltinRule::to_rule).collect(),
+        }
+    }
+
+    /// No rules, so every mood is as likely as any other.
+    pub fn none() -> Self {
+        Schedule { rules: Vec::new() }
+    }
+
+    /// Built-ins plus `schedule.toml`, whose rules must name moods in
+    /// `registry` (aliases are resolved to mood names).
+    pub fn load(registry: &MoodRegistry) -> Result<Self, String> {
+        let Some(path) = paths::config_dir().map(|dir| dir.join("schedule.toml")) else {
+            return Ok(Self::builtin());
+        };
+        if !path.exists() {
+            return Ok(Self::builtin());
+        }
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let mut file: ScheduleFile = toml::from_str(&contents)
+            .map_err(|e| format!("failed to parse {}: {}", path.display(), e))?;
+
+        for rule in &mut file.rules {
+            validate(rule, registry).map_err(|e| format!("{}: rule '{}' {}", path.display(), rule.name, e))?;
+        }
+        let mut schedule = if file.use_builtin {
+            Self::builtin()
+        } else {
+            Self::none()
+        };
+        schedule.rules.extend(file.rules);
+        Ok(schedule)
+    }
+
+    /// Every mood's weight at `at`, heaviest first. Moods start at 1 and
+    /// each matching rule multiplies in its weight.
+    pub fn weigh(&self, registry: &MoodRegistry, at: NaiveDateTime) -> Vec<Weight> {
+        let mut weights: Vec<Weight> = registry
+            .names()
+            .into_iter()
+            .map(|mood| Weight {
+                mood: mood.to_string(),
+                weight: 1.0,
+                reasons: Vec::new(),
+            })
+            .collect();
+        for rule in self.rules.iter().filter(|rule| rule.applies(at)) {
+            for weight in weights.iter_mut().filter(|w| rule.moods.iter().any(|m| m.eq_ignore_ascii_case(&w.mood))) {
+                weight.weight *= rule.weight;
+                weight.reasons.push((rule.name.clone(), rule.weight));
+            }
+        }
+        weights.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        weights
+    }
+}
+
+/// Draw a mood according to `weights`, uniformly if the rules ruled out
+/// every mood.
+pub fn choose<'a>(weights: &'a [Weight], rng: &mut impl Rng) -> &'a str {
+    match WeightedIndex::new(weights.iter().map(|w| w.weight)) {
+        Ok(index) => &weights[index.sample(rng)].mood,
+        Err(_) => &weights.choose(rng).expect("the registry always has the built-in moods").mood,
+    }
+}
+
+impl Rule {
+    fn applies(&self, at: NaiveDateTime) -> bool {
+        let hour = at.hour();
+        let in_hours = match self.hours.as_deref().and_then(parse_hours) {
+            Some((start, end)) if start <= end => (start..end).contains(&hour),
+            Some((start, end)) => hour >= start || hour < end,
+            None => true,
+        };
+        let weekday = at.weekday();
+        let on_day = self.days.is_empty() || self.days.iter().any(|day| day_matches(day, weekday));
+        in_hours && on_day
+    }
+}
+
+/// Check a rule from `schedule.toml`, and put its moods' names in place of
+/// aliases.
+fn validate(rule: &mut Rule, registry: &MoodRegistry) -> Result<(), String> {
+    if let Some(hours) = &rule.hours {
+        parse_hours(hours).ok_or_else(|| format!("has hours '{}', expected e.g. \"6-12\"", hours))?;
+    }
+    if let Some(day) = rule.days.iter().find(|day| !WEEK.iter().any(|w| day_matches(day, *w))) {
+        return Err(format!("has unknown day '{}'", day));
+    }
+    if !rule.weight.is_finite() || rule.weight < 0.0 {
+        return Err("needs a weight of 0 or more".to_string());
+    }
+    for mood in &mut rule.moods {
+        *mood = match registry.get(mood) {
+            Some(found) => found.name.clone(),
+            None => {
+                let hint = match registry.lookup(mood) {
+                    Lookup::Found(found) => format!(" (did you mean {}?)", found.name),
+                    Lookup::Ambiguous(candidates) => format!(" (it could be any of: {})", candidates.join(", ")),
+                    Lookup::Unknown(close) if !close.is_empty() => format!(" (did you mean {}?)", close.join(" or ")),
+                    Lookup::Unknown(_) => String::new(),
+                };
+                return Err(format!("has unknown mood '{}'{}", mood, hint));
+            }
+        };
+    }
+    Ok(())
+}
+
+const WEEK: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+fn parse_hours(hours: &str) -> Option<(u32, u32)> {
+    let (start, end) = hours.split_once('-')?;
+    let (start, end) = (start.trim().parse().ok()?, end.trim().parse().ok()?);
+    (start < 24 && end <= 24 && start != end).then_some((start, end))
+}
+
+fn day_matches(day: &str, weekday: Weekday) -> bool {
+    match day.to_lowercase().as_str() {
+        "weekdays" => weekday.num_days_from_monday() < 5,
+        "weekend" => weekday.num_days_from_monday() >= 5,
+        other => other.parse::<Weekday>().is_ok_and(|d| d == weekday),
+    }
+}
+
+/// Print the odds behind a random pick, for `--why`.
+pub fn print_why(weights: &[Weight], picked: &str, at: NaiveDateTime) {
+    let total: f64 = weights.iter().map(|w| w.weight).sum();
+    let width = weights.iter().map(|w| w.mood.len()).max().unwrap_or(0);
+    println!("{}", format!("🤔 Why {}? It's {}", picked, at.format("%A %H:%M")).bold());
+    for w in weights {
+        let share = if total > 0.0 { w.weight / total } else { 0.0 };
+        let filled = (share * 20.0).round() as usize;
+        let name = format!("{:<width$}", w.mood, width = width);
+        let mut line = format!(
+            "   {} {}{} {:>3.0}%",
+            if w.mood == picked { name.green().bold() } else { name.normal() },
+            "█".repeat(filled).cyan(),
+            "░".repeat(20 - filled).dimmed(),
+            share * 100.0
+        );
+        if !w.reasons.is_empty() {
+            let reasons: Vec<String> = w.reasons.iter().map(|(name, weight)| format!("×{} {}", weight, name)).collect();
+            line.push_str(&format!("  {}", reasons.join(", ").dimmed()));
+        }
+        println!("{}", line);
+    }
+    println!();
+}
+
+struct BuiltinRule {
+    name: &'static str,
+    moods: &'static [&'static str],
+    hours: Option<&'static str>,
+    days: &'static [&'static str],
+    weight: f64,
+}
+
+impl BuiltinRule {
+    fn to_rule(&self) -> Rule {
+        Rule {
+            name: self.name.to_string(),
+            moods: self.moods.iter().map(|m| m.to_string()).collect(),
+            hours: self.hours.map(str::to_string),
+            days: self.days.iter().map(|d| d.to_string()).collect(),
+            weight: self.weight,
+        }
+    }
+}
+
+const BUILTIN_RULES: &[BuiltinRule] = &[
+    BuiltinRule { name: "morning", moods: &["focus", "productive"], hours: Some("6-12"), days: &[], weight: 3.0 },
+    BuiltinRule { name: "afternoon", moods: &["energetic", "creative"], hours: Some("12-18"), days: &[], weight: 2.0 },
+    BuiltinRule { name: "evening", moods: &["chill", "zen"], hours: Some("18-23"), days: &[], weight: 3.0 },
+    BuiltinRule { name: "late night", moods: &["sadboi", "chaotic"], hours: Some("23-6"), days: &[], weight: 2.0 },
+    BuiltinRule { name: "friday", moods: &["nostalgic"], hours: None, days: &["fri"], weight: 3.0 },
+    BuiltinRule { name: "weekend", moods: &["adventurous", "creative"], hours: None, days: &["weekend"], weight: 2.0 },
+    BuiltinRule { name: "weekend", moods: &["productive"], hours: None, days: &["weekend"], weight: 0.5 },
+];
@@ -0,0 +1,433 @@
+use crate::{
+    history::{Entry, Event},
+    moods::MoodRegistry,
+    palette::{self, Rgb},
+    panel::{self, Colors},
+    pomodoro,
+};
+use chrono::{Datelike, Duration, NaiveDate};
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Everything recorded on one calendar day.
+#[derive(Debug, Default)]
+struct Day {
+    focused_minutes: f64,
+    focused_by_mood: HashMap<String, f64>,
+    picks_by_mood: HashMap<String, u32>,
+    completed_pomodoros: u32,
+}
+
+impl Day {
+    /// The mood that coloured the day: most focused minutes, falling back to
+    /// most picks when no timer ran.
+    fn dominant_mood(&self) -> Option<&str> {
+        let by_minutes = self
+            .focused_by_mood
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(b.1).then_with(|| b.0.cmp(a.0)));
+        if let Some((mood, _)) = by_minutes {
+            return Some(mood);
+        }
+        self.picks_by_mood
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(mood, _)| mood.as_str())
+    }
+
+    /// A day counts towards a streak once at least one pomodoro was finished.
+    fn is_active(&self) -> bool {
+        self.completed_pomodoros > 0
+    }
+}
+
+#[derive(Debug, Default)]
+struct MoodTotals {
+    picks: u32,
+    sessions: u32,
+    focused_minutes: f64,
+}
+
+/// Aggregates over the whole history, relative to `today`.
+pub struct Stats {
+    today: NaiveDate,
+    days: BTreeMap<NaiveDate, Day>,
+    moods: HashMap<String, MoodTotals>,
+    sessions: u32,
+    aborted_sessions: u32,
+    planned_pomodoros: u32,
+    completed_pomodoros: u32,
+}
+
+impl Stats {
+    pub fn new(entries: &[Entry], today: NaiveDate) -> Self {
+        let mut stats = Stats {
+            today,
+            days: BTreeMap::new(),
+            moods: HashMap::new(),
+            sessions: 0,
+            aborted_sessions: 0,
+            planned_pomodoros: 0,
+            completed_pomodoros: 0,
+        };
+
+        // Ratings say how a pick went, they aren't activity of their own
+        for entry in entries.iter().filter(|e| !matches!(e.event, Event::Rating { .. })) {
+            let day = stats.days.entry(entry.at().date_naive()).or_default();
+            let totals = stats.moods.entry(entry.mood.clone()).or_default();
+            match &entry.event {
+                Event::Pick { .. } => {
+                    *day.picks_by_mood.entry(entry.mood.clone()).or_default() += 1;
+                    totals.picks += 1;
+                }
+                Event::Pomodoro {
+                    actual_minutes,
+                    planned_pomodoros,
+                    completed_pomodoros,
+                    skipped_pomodoros,
+                    completed,
+                    ..
+                } => {
+                    day.focused_minutes += actual_minutes;
+                    *day.focused_by_mood.entry(entry.mood.clone()).or_default() += actual_minutes;
+                    day.completed_pomodoros += completed_pomodoros;
+                    totals.sessions += 1;
+                    totals.focused_minutes += actual_minutes;
+                    stats.sessions += 1;
+                    // Skipping pomodoros isn't giving up on the session
+                    stats.aborted_sessions += u32::from(!completed && completed_pomodoros + skipped_pomodoros < *planned_pomodoros);
+                    stats.planned_pomodoros += planned_pomodoros;
+                    stats.completed_pomodoros += completed_pomodoros;
+                }
+                Event::Rating { .. } => {}
+            }
+        }
+        stats
+    }
+
+    fn focused_between(&self, from: NaiveDate, to: NaiveDate) -> f64 {
+        self.days.range(from..=to).map(|(_, day)| day.focused_minutes).sum()
+    }
+
+    fn is_active(&self, date: NaiveDate) -> bool {
+        self.days.get(&date).is_some_and(Day::is_active)
+    }
+
+    /// Consecutive active days ending today. Today doesn't break the streak
+    /// until it's over, so an inactive today counts from yesterday.
+    fn current_streak(&self) -> u32 {
+        let mut date = self.today;
+        if !self.is_active(date) {
+            date -= Duration::days(1);
+        }
+        let mut streak = 0;
+        while self.is_active(date) {
+            streak += 1;
+            date -= Duration::days(1);
+        }
+        streak
+    }
+
+    fn longest_streak(&self) -> u32 {
+        let mut longest = 0;
+        let mut run = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for (&date, _) in self.days.iter().filter(|(_, day)| day.is_active()) {
+            run = match previous {
+                Some(prev) if date - prev == Duration::days(1) => run + 1,
+                _ => 1,
+            };
+            longest = longest.max(run);
+            previous = Some(date);
+        }
+        longest
+    }
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+fn minutes(minutes: f64) -> String {
+    match (minutes * 60.0).round() as u64 {
+        0 => "0m".to_string(),
+        seconds => pomodoro::format_duration(seconds),
+    }
+}
+
+fn bar(value: f64, max: f64, width: usize) -> String {
+    let filled = if max > 0.0 {
+        ((value / max) * width as f64).round() as usize
+    } else {
+        0
+    };
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// Heatmap shade for a day's focused minutes: none, under one pomodoro, up to
+/// two, up to four, more.
+fn shade(minutes: f64) -> &'static str {
+    match minutes {
+        m if m <= 0.0 => "·",
+        m if m < 25.0 => "░",
+        m if m < 50.0 => "▒",
+        m if m < 100.0 => "▓",
+        _ => "█",
+    }
+}
+
+pub fn print_report(stats: &Stats, registry: &MoodRegistry, weeks: u32) {
+    let color_of = |mood: &str| registry.get(mood).map_or(Rgb::from(Color::White), |m| m.palette.primary);
+    let today = stats.today;
+    let this_week = week_start(today);
+
+    println!();
+    panel::print_header("VIBE STATS", Colors::Named(Color::Cyan));
+    println!();
+
+    println!(
+        "⏱️  {}: today {} · this week {} · all time {}",
+        "Focused".yellow(),
+        minutes(stats.focused_between(today, today)).green(),
+        minutes(stats.focused_between(this_week, today)).green(),
+        minutes(stats.focused_between(NaiveDate::MIN, today)).green()
+    );
+    let rate = if stats.planned_pomodoros > 0 {
+        format!("{:.0}%", stats.completed_pomodoros as f64 * 100.0 / stats.planned_pomodoros as f64)
+    } else {
+        "n/a".to_string()
+    };
+    println!(
+        "🍅 {}: {}/{} completed ({}) · {} sessions, {} aborted",
+        "Pomodoros".yellow(),
+        stats.completed_pomodoros,
+        stats.planned_pomodoros,
+        rate.green(),
+        stats.sessions,
+        stats.aborted_sessions
+    );
+    println!(
+        "🔥 {}: current {} · longest {}",
+        "Streak".yellow(),
+        days(stats.current_streak()).green(),
+        days(stats.longest_streak()).green()
+    );
+    println!();
+
+    // Per day for the last week, per week for the last month
+    println!("{}", "Last 7 days".bold());
+    let daily: Vec<(NaiveDate, f64)> = (0..7)
+        .rev()
+        .map(|n| today - Duration::days(n))
+        .map(|date| (date, stats.focused_between(date, date)))
+        .collect();
+    let max = daily.iter().map(|(_, m)| *m).fold(0.0, f64::max);
+    for (date, focused) in &daily {
+        println!("  {}  {}  {}", date.format("%a %m-%d"), bar(*focused, max, 20).green(), minutes(*focused));
+    }
+    println!();
+
+    println!("{}", "Last 4 weeks".bold());
+    let weekly: Vec<(NaiveDate, f64)> = (0..4)
+        .rev()
+        .map(|n| this_week - Duration::weeks(n))
+        .map(|start| (start, stats.focused_between(start, start + Duration::days(6))))
+        .collect();
+    let max = weekly.iter().map(|(_, m)| *m).fold(0.0, f64::max);
+    for (start, focused) in &weekly {
+        println!("  {}  {}  {}", start.format("wk %m-%d"), bar(*focused, max, 20).green(), minutes(*focused));
+    }
+    println!();
+
+    let mut moods: Vec<(&String, &MoodTotals)> = stats.moods.iter().collect();
+    moods.sort_by(|a, b| {
+        (b.1.picks + b.1.sessions)
+            .cmp(&(a.1.picks + a.1.sessions))
+            .then_with(|| a.0.cmp(b.0))
+    });
+    if !moods.is_empty() {
+        println!("{}", "Top moods".bold());
+        let max = moods.iter().map(|(_, t)| (t.picks + t.sessions) as f64).fold(0.0, f64::max);
+        for (mood, totals) in moods.iter().take(5) {
+            println!(
+                "  {}  {}  {} picks · {} sessions · {} focused",
+                palette::fg(&format!("{:<12}", mood), color_of(mood)),
+                palette::fg(&bar((totals.picks + totals.sessions) as f64, max, 12), color_of(mood)),
+                totals.picks,
+                totals.sessions,
+                minutes(totals.focused_minutes)
+            );
+        }
+        println!();
+    }
+
+    print_heatmap(stats, weeks, &color_of);
+}
+
+fn days(n: u32) -> String {
+    format!("{} {}", n, if n == 1 { "day" } else { "days" })
+}
+
+/// GitHub-style calendar: one column per week, one row per weekday, each
+/// cell shaded by focused minutes and colored by that day's dominant mood.
+fn print_heatmap(stats: &Stats, weeks: u32, color_of: &dyn Fn(&str) -> Rgb) {
+    let weeks = weeks.max(1) as i64;
+    let first = week_start(stats.today) - Duration::weeks(weeks - 1);
+    println!("{}", format!("Activity, last {} weeks", weeks).bold());
+
+    // Month labels above the first column of each month
+    let mut header = String::from("      ");
+    let mut last_month = None;
+    let mut skip = 0;
+    for week in 0..weeks {
+        let start = first + Duration::weeks(week);
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+        if last_month != Some(start.month()) {
+            last_month = Some(start.month());
+            header.push_str(&format!("{:<4}", start.format("%b")));
+            skip = 1;
+        } else {
+            header.push_str("  ");
+        }
+    }
+    println!("{}", header.trim_end().dimmed());
+
+    let mut seen_moods: Vec<String> = Vec::new();
+    for weekday in 0..7 {
+        let label = match weekday {
+            0 => "Mon",
+            2 => "Wed",
+            4 => "Fri",
+            _ => "",
+        };
+        let mut row = format!("  {:<4}", label).dimmed().to_string();
+        for week in 0..weeks {
+            let date = first + Duration::weeks(week) + Duration::days(weekday);
+            if date > stats.today {
+                break;
+            }
+            let cell = match stats.days.get(&date) {
+                Some(day) => match day.dominant_mood() {
+                    Some(mood) => {
+                        if !seen_moods.iter().any(|m| m == mood) {
+                            seen_moods.push(mood.to_string());
+                        }
+                        let symbol = if day.focused_minutes > 0.0 { shade(day.focused_minutes) } else { "░" };
+                        palette::fg(symbol, color_of(mood))
+                    }
+                    None => "·".dimmed().to_string(),
+                },
+                None => "·".dimmed().to_string(),
+            };
+            row.push_str(&cell);
+            row.push(' ');
+        }
+        println!("{}", row.trim_end());
+    }
+
+    println!();
+    println!("  {} · ░ ▒ ▓ █ {}", "Less".dimmed(), "More".dimmed());
+    if !seen_moods.is_empty() {
+        seen_moods.sort();
+        let legend: Vec<String> = seen_moods
+            .iter()
+            .map(|mood| format!("{} {}", palette::fg("█", color_of(mood)), mood))
+            .collect();
+        println!("  {}", legend.join("  "));
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> chrono::DateTime<Local> {
+        Local.from_local_datetime(&date(day).and_hms_opt(hour, 0, 0).unwrap()).unwrap()
+    }
+
+    fn pomodoro(day: u32, mood: &str, minutes: f64, completed_pomodoros: u32) -> Entry {
+        Entry {
+            id: 0,
+            mood: mood.to_string(),
+            variant: None,
+            event: Event::Pomodoro {
+                started_at: at(day, 9),
+                ended_at: at(day, 10),
+                planned_minutes: 25,
+                actual_minutes: minutes,
+                planned_pomodoros: 1,
+                completed_pomodoros,
+                skipped_pomodoros: 0,
+                completed: completed_pomodoros > 0,
+            },
+        }
+    }
+
+    fn pick(day: u32, mood: &str) -> Entry {
+        Entry {
+            id: 0,
+            mood: mood.to_string(),
+            variant: None,
+            event: Event::Pick { at: at(day, 12) },
+        }
+    }
+
+    #[test]
+    fn streak_runs_through_today() {
+        let entries: Vec<Entry> = [8, 9, 10].iter().map(|&day| pomodoro(day, "focus", 25.0, 1)).collect();
+        let stats = Stats::new(&entries, date(10));
+        assert_eq!(stats.current_streak(), 3);
+        assert_eq!(stats.longest_streak(), 3);
+    }
+
+    #[test]
+    fn inactive_today_keeps_yesterdays_streak() {
+        let mut entries: Vec<Entry> = [8, 9].iter().map(|&day| pomodoro(day, "focus", 25.0, 1)).collect();
+        // Picks and unfinished pomodoros aren't activity
+        entries.push(pick(10, "zen"));
+        entries.push(pomodoro(10, "zen", 3.0, 0));
+        let stats = Stats::new(&entries, date(10));
+        assert_eq!(stats.current_streak(), 2);
+        // Two days without a pomodoro do end it
+        assert_eq!(Stats::new(&entries, date(11)).current_streak(), 0);
+    }
+
+    #[test]
+    fn gap_splits_streaks() {
+        let entries: Vec<Entry> = [1, 2, 3, 5, 6].iter().map(|&day| pomodoro(day, "focus", 25.0, 1)).collect();
+        let stats = Stats::new(&entries, date(6));
+        assert_eq!(stats.current_streak(), 2);
+        assert_eq!(stats.longest_streak(), 3);
+        assert_eq!(Stats::new(&[], date(6)).longest_streak(), 0);
+    }
+
+    #[test]
+    fn dominant_mood_prefers_focus_time_then_picks() {
+        let entries = vec![
+            pick(1, "chill"),
+            pick(1, "chill"),
+            pomodoro(1, "zen", 10.0, 0),
+            pomodoro(1, "focus", 25.0, 1),
+            pick(2, "chill"),
+            pick(2, "zen"),
+            pick(2, "zen"),
+            pick(3, "zen"),
+            pick(3, "chill"),
+        ];
+        let stats = Stats::new(&entries, date(3));
+        assert_eq!(stats.days[&date(1)].dominant_mood(), Some("focus"));
+        assert_eq!(stats.days[&date(2)].dominant_mood(), Some("zen"));
+        // A tie goes to the name first in alphabetical order
+        assert_eq!(stats.days[&date(3)].dominant_mood(), Some("chill"));
+        assert_eq!(Day::default().dominant_mood(), None);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    effects,
+    moods::MoodRegistry,
+    palette,
+    panel::{self, fit, Colors},
+};
+use colored::*;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    panic,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Puts the terminal in raw mode (and optionally the alternate screen) for as
+/// long as it lives. Dropping it, or panicking while it is alive, restores the
+/// terminal so the user never gets left with a hidden cursor.
+pub struct RawTerminal {
+    alternate_screen: bool,
+}
+
+impl RawTerminal {
+    pub fn enter(alternate_screen: bool) -> io::Result<Self> {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore(alternate_screen);
+            previous_hook(info);
+        }));
+
+        terminal::enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        if alternate_screen {
+            execute!(stdout, terminal::EnterAlternateScreen)?;
+        }
+        execute!(stdout, cursor::Hide)?;
+        Ok(RawTerminal { alternate_screen })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        restore(self.alternate_screen);
+        // Hand panics back to the default hook
+        let _ = panic::take_hook();
+    }
+}
+
+fn restore(alternate_screen: bool) {
+    let mut stdout = io::stdout();
+    let _ = execute!(stdout, cursor::Show);
+    if alternate_screen {
+        let _ = execute!(stdout, terminal::LeaveAlternateScreen);
+    }
+    let _ = terminal::disable_raw_mode();
+}
+
+/// Whether a key event should cancel whatever is running. Raw mode swallows
+/// SIGINT, so Ctrl+C arrives here as an ordinary key press.
+pub fn is_interrupt(key: &KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c')
+}
+
+/// Full-screen mood picker. Returns the chosen mood, or `None` if the user
+/// backed out.
+pub fn select_mood(registry: &MoodRegistry) -> io::Result<Option<String>> {
+    let moods = registry.names();
+    let mut selected = 0;
+    let _terminal = RawTerminal::enter(true)?;
+    let mut stdout = io::stdout();
+
+    loop {
+        draw_selector(&mut stdout, registry, &moods, selected)?;
+
+        // Resize events simply fall through to the redraw above
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if is_interrupt(&key) {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(moods.len() - 1),
+            KeyCode::Home | KeyCode::Char('g') => selected = 0,
+            KeyCode::End | KeyCode::Char('G') => selected = moods.len() - 1,
+            KeyCode::Char(c @ '1'..='9') => {
+                let index = c as usize - '1' as usize;
+                if index < moods.len() {
+                    selected = index;
+                }
+            }
+            KeyCode::Enter => return Ok(Some(moods[selected].to_string())),
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 12;
+
+fn draw_selector(
+    out: &mut impl Write,
+    registry: &MoodRegistry,
+    moods: &[&str],
+    selected: usize,
+) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    queue!(out, terminal::Clear(ClearType::All))?;
+
+    if width < MIN_WIDTH || height < MIN_HEIGHT {
+        put(out, 0, 0, "Terminal too small for the vibe selector".yellow())?;
+        put(out, 0, 1, format!("Need {}x{}, have {}x{}", MIN_WIDTH, MIN_HEIGHT, width, height))?;
+        put(out, 0, 2, "Resize, or press q to quit".dimmed())?;
+        return out.flush();
+    }
+
+    let (width, height) = (width as usize, height as usize);
+    let title = " 🎵 VIBE SELECTOR 🎵 ";
+    put(out, 0, 0, panel::top_rule(title, width).cyan())?;
+
+    // Left column: the mood list, scrolled so the selection stays visible
+    let list_width = moods.iter().map(|m| m.width()).max().unwrap_or(0).max(12) + 6;
+    let body_top = 2;
+    let body_height = height - body_top - 2;
+    let scroll = selected.saturating_sub(body_height - 1);
+    for (row, (i, mood)) in moods.iter().enumerate().skip(scroll).take(body_height).enumerate() {
+        let y = (body_top + row) as u16;
+        if i == selected {
+            put(out, 1, y, format!("▶ {}", mood.to_uppercase()).green().bold())?;
+        } else {
+            put(out, 1, y, format!("  {}", mood))?;
+        }
+    }
+    for row in 0..body_height {
+        put(out, list_width as u16, (body_top + row) as u16, "│".blue())?;
+    }
+
+    // Right column: preview of the highlighted mood
+    let preview_x = list_width + 2;
+    let preview_width = width - preview_x - 1;
+    let mut lines: Vec<String> = vec![format!("{}", "PREVIEW".blue().bold()), String::new()];
+    if let Some(mood) = registry.get(moods[selected]) {
+        for (icon, label, value) in [
+            ("🎨", "Theme", &mood.theme),
+            ("🎵", "Music", &mood.music),
+            ("⚡", "Stack", &mood.stack),
+        ] {
+            lines.extend(panel::field(icon, label, value, Colors::Palette(mood.palette), preview_width));
+        }
+        lines.extend(panel::field_with("💭", "Motto", &mood.motto, Colors::Palette(mood.palette), preview_width, |parts| {
+            effects::render_lines(parts, mood.effect, &mood.gradient_stops(), 0.0)
+        }));
+        if let Some(art) = &mood.ascii_art {
+            lines.push(String::new());
+            let art: Vec<String> = art.trim_matches('\n').lines().map(|line| fit(line, preview_width)).collect();
+            lines.extend(effects::render_lines(&art, mood.effect, &mood.gradient_stops(), 0.0));
+        }
+    }
+    for (row, line) in lines.iter().take(body_height).enumerate() {
+        put(out, preview_x as u16, (body_top + row) as u16, line)?;
+    }
+
+    let footer = "↑↓/jk move · 1-9 jump · Enter select · q/Esc quit";
+    put(out, 1, (height - 1) as u16, fit(footer, width - 2).dimmed())?;
+    out.flush()
+}
+
+/// Cursor steps per axis on the mood grid, so each key press moves 0.05.
+const GRID_STEPS: i32 = 20;
+
+/// Mood picker on the energy/positivity plane: move a cursor around and take
+/// whichever mood is nearest. Returns `None` if the user backed out.
+pub fn select_on_grid(registry: &MoodRegistry) -> io::Result<Option<String>> {
+    let (mut energy, mut positivity) = (GRID_STEPS / 2, GRID_STEPS / 2);
+    let _terminal = RawTerminal::enter(true)?;
+    let mut stdout = io::stdout();
+
+    loop {
+        let at = |steps: i32| steps as f64 / GRID_STEPS as f64;
+        let nearest = registry.nearest(at(energy), at(positivity));
+        draw_grid(&mut stdout, registry, at(energy), at(positivity), &nearest.name)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if is_interrupt(&key) {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => energy = (energy + 1).min(GRID_STEPS),
+            KeyCode::Down | KeyCode::Char('j') => energy = (energy - 1).max(0),
+            KeyCode::Right | KeyCode::Char('l') => positivity = (positivity + 1).min(GRID_STEPS),
+            KeyCode::Left | KeyCode::Char('h') => positivity = (positivity - 1).max(0),
+            KeyCode::Enter => return Ok(Some(nearest.name.clone())),
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+const MIN_GRID_HEIGHT: u16 = 16;
+
+fn draw_grid(
+    out: &mut impl Write,
+    registry: &MoodRegistry,
+    energy: f64,
+    positivity: f64,
+    nearest: &str,
+) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    queue!(out, terminal::Clear(ClearType::All))?;
+
+    if width < MIN_WIDTH || height < MIN_GRID_HEIGHT {
+        put(out, 0, 0, "Terminal too small for the mood grid".yellow())?;
+        put(out, 0, 1, format!("Need {}x{}, have {}x{}", MIN_WIDTH, MIN_GRID_HEIGHT, width, height))?;
+        put(out, 0, 2, "Resize, or press q to quit".dimmed())?;
+        return out.flush();
+    }
+
+    let (width, height) = (width as usize, height as usize);
+    let title = " 🧭 MOOD GRID 🧭 ";
+    put(out, 0, 0, panel::top_rule(title, width).cyan())?;
+
+    // Plot area: positivity grows to the right, energy grows upwards
+    let (left, right) = (4, width - 3);
+    let (top, bottom) = (2, height - 5);
+    let column = |p: f64| left + (p * (right - left) as f64).round() as usize;
+    let row = |e: f64| bottom - (e * (bottom - top) as f64).round() as usize;
+
+    put(out, 0, top as u16, "⚡".yellow())?;
+    put(out, 0, bottom as u16, "💤".blue())?;
+    for y in top..=bottom {
+        put(out, 3, y as u16, "│".blue())?;
+    }
+    put(out, 3, (bottom + 1) as u16, format!("└{}", "─".repeat(right - left + 1)).blue())?;
+    let axis = "unpleasant ← positivity → pleasant";
+    put(out, (left + (right - left).saturating_sub(axis.width()) / 2) as u16, (bottom + 2) as u16, axis.dimmed())?;
+
+    for mood in registry.all() {
+        let (x, y) = (column(mood.positivity), row(mood.energy));
+        let color = mood.palette.primary;
+        let highlight = mood.name == nearest;
+        let name = if highlight { mood.name.to_uppercase() } else { mood.name.clone() };
+        // Near the right edge the label goes left of its dot so it stays on
+        // screen
+        let (label, label_x) = if x + name.width() + 2 > width - 1 {
+            (format!("{} ●", name), (x + 1).saturating_sub(name.width() + 2))
+        } else {
+            (format!("● {}", name), x)
+        };
+        let label = if highlight { palette::bold(&label, color) } else { palette::fg(&label, color) };
+        put(out, label_x as u16, y as u16, label)?;
+    }
+    put(out, column(positivity) as u16, row(energy) as u16, "✛".white().bold())?;
+
+    let description = registry.get(nearest).map_or("", |m| m.description.as_str());
+    let status = format!(
+        "📍 energy {:.2} · positivity {:.2} → {}: {}",
+        energy, positivity, nearest, description
+    );
+    put(out, 1, (height - 2) as u16, fit(&status, width - 2))?;
+    let footer = "↑↓/jk energy · ←→/hl positivity · Enter select · q/Esc quit";
+    put(out, 1, (height - 1) as u16, fit(footer, width - 2).dimmed())?;
+    out.flush()
+}
+
+fn put(out: &mut impl Write, x: u16, y: u16, text: impl Display) -> io::Result<()> {
+    queue!(out, cursor::MoveTo(x, y), Print(text))
+}
@@ -0,0 +1,419 @@
+use crate::moods::Mood;
+use serde_json::{json, Map, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Extensions recommended when a stack mentions the given technology.
+/// Stacks are matched word by word, case-insensitively.
+const STACK_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("rust", &["rust-lang.rust-analyzer", "tamasfe.even-better-toml"]),
+    ("typescript", &["dbaeumer.vscode-eslint", "esbenp.prettier-vscode"]),
+    ("javascript", &["dbaeumer.vscode-eslint", "esbenp.prettier-vscode"]),
+    ("node.js", &["dbaeumer.vscode-eslint", "esbenp.prettier-vscode"]),
+    ("react", &["dsznajder.es7-react-js-snippets"]),
+    ("python", &["ms-python.python", "charliermarsh.ruff"]),
+    ("flask", &["ms-python.python"]),
+    ("go", &["golang.go"]),
+    ("java", &["vscjava.vscode-java-pack"]),
+    ("spring", &["vmware.vscode-boot-dev-pack"]),
+    ("c++", &["ms-vscode.cpptools-extension-pack"]),
+    ("haskell", &["haskell.haskell"]),
+    ("postgresql", &["ckolkman.vscode-postgres"]),
+    ("mysql", &["cweijan.vscode-mysql-client2"]),
+    ("sqlite", &["qwtel.sqlite-viewer"]),
+    ("mongodb", &["mongodb.mongodb-vscode"]),
+    ("redis", &["cweijan.vscode-redis-client"]),
+    ("docker", &["ms-azuretools.vscode-docker"]),
+    ("kubernetes", &["ms-kubernetes-tools.vscode-kubernetes-tools"]),
+    ("firebase", &["toba.vsfire"]),
+    ("html", &["ecmel.vscode-html-css"]),
+    ("css", &["ecmel.vscode-html-css"]),
+    ("webgl", &["slevesque.shader"]),
+    ("opengl", &["slevesque.shader"]),
+    ("three.js", &["slevesque.shader"]),
+    ("webassembly", &["dtsvet.vscode-wasm"]),
+];
+
+/// Distraction-free settings applied for moods with `vscode_zen` set.
+fn zen_settings() -> Map<String, Value> {
+    let settings = json!({
+        "editor.minimap.enabled": false,
+        "breadcrumbs.enabled": false,
+        "workbench.activityBar.location": "hidden",
+        "workbench.statusBar.visible": false,
+        "zenMode.centerLayout": true,
+        "zenMode.silentNotifications": true,
+    });
+    match settings {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+/// A file `--workspace` wants to create or update.
+pub struct FileChange {
+    pub path: PathBuf,
+    pub before: String,
+    pub after: String,
+    /// Where the original is copied before it's rewritten, if it has
+    /// comments that rewriting would lose
+    pub backup: Option<PathBuf>,
+}
+
+impl FileChange {
+    pub fn is_unchanged(&self) -> bool {
+        self.before == self.after
+    }
+}
+
+/// Work out the new contents of `.vscode/settings.json` and
+/// `.vscode/extensions.json` under `dir` without touching the disk.
+pub fn plan(dir: &Path, mood: &Mood) -> Result<Vec<FileChange>, String> {
+    let vscode = dir.join(".vscode");
+
+    let settings_path = vscode.join("settings.json");
+    let before = read_or_empty(&settings_path)?;
+    let mut settings = parse_object(&before, &settings_path)?;
+    settings.insert("workbench.colorTheme".to_string(), json!(mood.vscode_theme));
+    if mood.vscode_zen {
+        // Settings the user already chose win over the zen ones
+        for (key, value) in zen_settings() {
+            settings.entry(key).or_insert(value);
+        }
+    }
+    let settings_change = FileChange {
+        after: to_pretty_json(&settings),
+        backup: backup_path(&before, &settings_path),
+        path: settings_path,
+        before,
+    };
+
+    let extensions_path = vscode.join("extensions.json");
+    let before = read_or_empty(&extensions_path)?;
+    let mut extensions = parse_object(&before, &extensions_path)?;
+    let mut recommendations: Vec<Value> = match extensions.remove("recommendations") {
+        Some(Value::Array(existing)) => existing,
+        Some(_) => {
+            return Err(format!(
+                "{}: \"recommendations\" is not a list",
+                extensions_path.display()
+            ))
+        }
+        None => Vec::new(),
+    };
+    for id in recommended_extensions(mood) {
+        let id = json!(id);
+        if !recommendations.contains(&id) {
+            recommendations.push(id);
+        }
+    }
+    extensions.insert("recommendations".to_string(), Value::Array(recommendations));
+    let extensions_change = FileChange {
+        after: to_pretty_json(&extensions),
+        backup: backup_path(&before, &extensions_path),
+        path: extensions_path,
+        before,
+    };
+
+    Ok(vec![settings_change, extensions_change])
+}
+
+/// Write every changed file, creating `.vscode/` as needed.
+pub fn apply(changes: &[FileChange]) -> Result<(), String> {
+    for change in changes.iter().filter(|c| !c.is_unchanged()) {
+        if let Some(parent) = change.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+        }
+        if let Some(backup) = &change.backup {
+            fs::write(backup, &change.before)
+                .map_err(|e| format!("failed to write {}: {}", backup.display(), e))?;
+        }
+        fs::write(&change.path, &change.after)
+            .map_err(|e| format!("failed to write {}: {}", change.path.display(), e))?;
+    }
+    Ok(())
+}
+
+fn recommended_extensions(mood: &Mood) -> Vec<&str> {
+    let stack = mood.stack.to_lowercase();
+    let words: Vec<&str> = stack
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '/' | '(' | ')'))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut ids: Vec<&str> = mood.vscode_theme_extension.as_deref().into_iter().collect();
+    for (keyword, extensions) in STACK_EXTENSIONS {
+        if words.contains(keyword) {
+            for id in *extensions {
+                if !ids.contains(id) {
+                    ids.push(*id);
+                }
+            }
+        }
+    }
+    ids
+}
+
+fn read_or_empty(path: &Path) -> Result<String, String> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))
+}
+
+/// `<path>.bak` if `contents` has comments, which don't survive the rewrite.
+fn backup_path(contents: &str, path: &Path) -> Option<PathBuf> {
+    let has_comments = strip_jsonc(contents) != strip_trailing_commas(contents);
+    has_comments.then(|| {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(".bak");
+        PathBuf::from(backup)
+    })
+}
+
+fn parse_object(contents: &str, path: &Path) -> Result<Map<String, Value>, String> {
+    if contents.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str(&strip_jsonc(contents)) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(format!("{} is not a JSON object", path.display())),
+        Err(e) => Err(format!("failed to parse {}: {}", path.display(), e)),
+    }
+}
+
+/// VS Code's settings files are JSON with comments and trailing commas. Drop
+/// both so serde_json can read them; comments are not carried over.
+fn strip_jsonc(contents: &str) -> String {
+    let mut out = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|&n| n != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = '\0';
+                for n in chars.by_ref() {
+                    if prev == '*' && n == '/' {
+                        break;
+                    }
+                    prev = n;
+                }
+            }
+            _ => {
+                in_string = c == '"';
+                out.push(c);
+            }
+        }
+    }
+    strip_trailing_commas(&out)
+}
+
+fn strip_trailing_commas(json: &str) -> String {
+    let chars: Vec<char> = json.chars().collect();
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = chars[i + 1..].iter().find(|n| !n.is_whitespace());
+            if matches!(next, Some('}' | ']')) {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn to_pretty_json(map: &Map<String, Value>) -> String {
+    use serde::Serialize;
+
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    map.serialize(&mut serializer)
+        .expect("serializing a JSON map cannot fail");
+    let mut json = String::from_utf8(buf).expect("serde_json writes UTF-8");
+    json.push('\n');
+    json
+}
+
+/// One line of a line-based diff: ' ' unchanged, '-' removed, '+' added.
+pub fn diff_lines<'a>(before: &'a str, after: &'a str) -> Vec<(char, &'a str)> {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+
+    // Longest common subsequence table, filled from the end
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| ('-', *l)));
+    lines.extend(new[j..].iter().map(|l| ('+', *l)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moods::MoodRegistry;
+
+    /// A project directory of its own in the temp directory, removed when
+    /// the test ends.
+    struct TempProject(PathBuf);
+
+    impl TempProject {
+        fn new(name: &str, settings: Option<&str>) -> TempProject {
+            let dir = std::env::temp_dir().join(format!("vibe-workspace-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(dir.join(".vscode")).unwrap();
+            if let Some(settings) = settings {
+                fs::write(dir.join(".vscode/settings.json"), settings).unwrap();
+            }
+            TempProject(dir)
+        }
+    }
+
+    impl Drop for TempProject {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn settings(changes: &[FileChange]) -> Value {
+        serde_json::from_str(&changes[0].after).unwrap()
+    }
+
+    #[test]
+    fn strip_jsonc_keeps_comment_markers_inside_strings() {
+        let jsonc = r#"{
+            // line comment
+            "url": "http://example.com/*not a comment*/",
+            "escaped": "a \"// quote\" inside", /* block
+            comment */ "after": 1
+        }"#;
+        let value: Value = serde_json::from_str(&strip_jsonc(jsonc)).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "url": "http://example.com/*not a comment*/",
+                "escaped": "a \"// quote\" inside",
+                "after": 1,
+            })
+        );
+    }
+
+    #[test]
+    fn strip_jsonc_drops_trailing_commas() {
+        let jsonc = r#"{ "list": [1, 2, ], "nested": { "a": "x,}", }, }"#;
+        assert_eq!(strip_jsonc(jsonc), r#"{ "list": [1, 2 ], "nested": { "a": "x,}" } }"#);
+    }
+
+    #[test]
+    fn backup_only_for_files_with_comments() {
+        let path = Path::new("/project/.vscode/settings.json");
+        assert_eq!(backup_path("{ \"a\": 1, }", path), None);
+        assert_eq!(backup_path("{ \"url\": \"http://x\" }", path), None);
+        let backup = Some(PathBuf::from("/project/.vscode/settings.json.bak"));
+        assert_eq!(backup_path("{ // mine\n \"a\": 1 }", path), backup);
+        assert_eq!(backup_path("{ /* mine */ \"a\": 1 }", path), backup);
+    }
+
+    #[test]
+    fn user_settings_win_over_zen_ones() {
+        let registry = MoodRegistry::builtin();
+        let focus = registry.get("focus").unwrap();
+        assert!(focus.vscode_zen);
+        let project = TempProject::new("zen", Some("{\n  // keep the minimap\n  \"editor.minimap.enabled\": true,\n}\n"));
+
+        let changes = plan(&project.0, focus).unwrap();
+        let settings = settings(&changes);
+        assert_eq!(settings["editor.minimap.enabled"], json!(true));
+        assert_eq!(settings["breadcrumbs.enabled"], json!(false));
+        assert_eq!(settings["workbench.colorTheme"], json!(focus.vscode_theme));
+        assert_eq!(changes[0].backup, Some(project.0.join(".vscode/settings.json.bak")));
+
+        apply(&changes).unwrap();
+        let backup = fs::read_to_string(project.0.join(".vscode/settings.json.bak")).unwrap();
+        assert!(backup.contains("// keep the minimap"));
+        // Written back without comments, so there's nothing more to back up
+        let again = plan(&project.0, focus).unwrap();
+        assert!(again[0].is_unchanged());
+        assert_eq!(again[0].backup, None);
+    }
+
+    #[test]
+    fn plan_starts_from_nothing() {
+        let registry = MoodRegistry::builtin();
+        let chill = registry.get("chill").unwrap();
+        let project = TempProject::new("empty", None);
+        let changes = plan(&project.0, chill).unwrap();
+        assert_eq!(changes[0].before, "");
+        assert_eq!(changes[0].backup, None);
+        assert_eq!(settings(&changes), json!({ "workbench.colorTheme": chill.vscode_theme }));
+    }
+
+    #[test]
+    fn diff_lines_marks_changes() {
+        let before = "{\n    \"a\": 1,\n    \"b\": 2\n}";
+        let after = "{\n    \"a\": 1,\n    \"b\": 3,\n    \"c\": 4\n}";
+        assert_eq!(
+            diff_lines(before, after),
+            [
+                (' ', "{"),
+                (' ', "    \"a\": 1,"),
+                ('-', "    \"b\": 2"),
+                ('+', "    \"b\": 3,"),
+                ('+', "    \"c\": 4"),
+                (' ', "}"),
+            ]
+        );
+    }
+}